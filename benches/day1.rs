@@ -0,0 +1,90 @@
+//! Decodes a generated calibration document of several megabytes, with the automaton of the
+//! CalibrationDecoder and with the first parser that tried every slice at every byte
+//! Run with `cargo bench --bench day1`
+
+use std::time::{Duration, Instant};
+
+use advent2023::day1::{CalibrationDecoder, Mode};
+
+const SIZE: usize = 16 << 20;
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn parse(line: &str) -> i32 {
+    let mut words_to_int = std::collections::HashMap::new();
+    for (digit, word) in (1..).zip(WORDS) {
+        words_to_int.insert(word, digit);
+    }
+    let mut digits = vec![];
+    for i in 0..line.len() {
+        if let Some(d) = line.get(i..i + 1).and_then(|d| d.parse::<i32>().ok()) {
+            digits.push(d)
+        } else if let Some(d) = line.get(i..i + 5).and_then(|d| words_to_int.get(d)) {
+            digits.push(*d)
+        } else if let Some(d) = line.get(i..i + 4).and_then(|d| words_to_int.get(d)) {
+            digits.push(*d)
+        } else if let Some(d) = line.get(i..i + 3).and_then(|d| words_to_int.get(d)) {
+            digits.push(*d)
+        }
+    }
+
+    let decimal = digits.first().expect("missing first digit");
+    let unit = digits.last().expect("missing last digit");
+    decimal * 10 + unit
+}
+
+// xorshift64, so that every run decodes the same document
+struct Random(u64);
+
+impl Random {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+fn generate(size: usize) -> String {
+    let mut random = Random(0x2023_1201);
+    let mut document = String::with_capacity(size + 100);
+    while document.len() < size {
+        for _ in 0..5 + random.below(40) {
+            match random.below(20) {
+                0 => document.push_str(WORDS[random.below(9)]),
+                1 => document.push(char::from(b'1' + random.below(9) as u8)),
+                _ => document.push(char::from(b'a' + random.below(26) as u8)),
+            }
+        }
+        // Every line needs at least one digit
+        document.push(char::from(b'1' + random.below(9) as u8));
+        document.push('\n');
+    }
+    document
+}
+
+fn measure<T>(name: &str, bytes: usize, f: impl Fn() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed().max(Duration::from_nanos(1));
+    let throughput = bytes as f64 / elapsed.as_secs_f64() / (1 << 20) as f64;
+    println!("{name:>10}: {elapsed:>12.2?} ({throughput:.1} MiB/s)");
+    result
+}
+
+fn main() {
+    let document = generate(SIZE);
+    println!(
+        "{} MiB, {} lines",
+        document.len() >> 20,
+        document.lines().count()
+    );
+
+    let expected = measure("parse", document.len(), || {
+        document.lines().map(|line| parse(line) as u64).sum::<u64>()
+    });
+    let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+    let decoded = measure("automaton", document.len(), || decoder.sum(&document));
+    assert_eq!(Ok(expected), decoded);
+}
@@ -0,0 +1,395 @@
+use std::io::Read;
+use std::ops::RangeInclusive;
+
+use advent2023::day1::{CalibrationDecoder, Mode};
+use advent2023::day10::render;
+use advent2023::day2::{self, Bag, Format};
+use advent2023::PuzzleError;
+
+const USAGE: &str = "usage: aoc run <day|first-last|all> [--part 1|2] [--input path|-]
+       aoc calibrate <path|-> [--part 1|2] [--verbose]
+       aoc cubes [--input path|-] [--bag \"12 red, 13 green\" | --bag-file path]
+                 [--at-least k] [--report table|csv]
+       aoc render 10 [--input path|-] [--colour] [--svg out.svg]";
+
+struct Args {
+    days: RangeInclusive<u32>,
+    part: Option<u32>,
+    input: Option<String>, // - is the standard input
+}
+
+// Streams a day 1 calibration document, that can be too large to be read at once
+struct Calibrate {
+    input: String,
+    mode: Mode,
+    verbose: bool,
+}
+
+// Explores the day 2 games with other bags than the one of the puzzle
+struct Cubes {
+    input: String,
+    bag: Option<String>,
+    bag_file: Option<String>,
+    at_least: Option<usize>,
+    report: Option<Format>,
+}
+
+// Draws the day 10 loop in the terminal, and optionally as an SVG file
+struct Render {
+    input: String,
+    colours: bool,
+    svg: Option<String>,
+}
+
+enum Command {
+    Run(Args),
+    Calibrate(Calibrate),
+    Cubes(Cubes),
+    Render(Render),
+}
+
+fn parse_days(days: &str) -> Result<RangeInclusive<u32>, String> {
+    let day = |d: &str| match d.parse::<u32>() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("invalid day: {d} (days go from 1 to 25)")),
+    };
+    match days.split_once('-') {
+        _ if days == "all" => Ok(1..=25),
+        Some((first, last)) => {
+            let (first, last) = (day(first)?, day(last)?);
+            if first > last {
+                return Err(format!(
+                    "invalid range: {days} (the first day comes after the last)"
+                ));
+            }
+            Ok(first..=last)
+        }
+        None => day(days).map(|d| d..=d),
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("calibrate") => parse_calibrate(args).map(Command::Calibrate),
+        Some("cubes") => parse_cubes(args).map(Command::Cubes),
+        Some("render") => parse_render(args).map(Command::Render),
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn parse_calibrate(mut args: impl Iterator<Item = String>) -> Result<Calibrate, String> {
+    let mut parsed = Calibrate {
+        input: args.next().ok_or(USAGE)?,
+        mode: Mode::DigitsAndWords,
+        verbose: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--verbose" => parsed.verbose = true,
+            "--part" => match args.next().as_deref() {
+                Some("1") => parsed.mode = Mode::Digits,
+                Some("2") => parsed.mode = Mode::DigitsAndWords,
+                value => return Err(format!("invalid part: {}", value.unwrap_or(""))),
+            },
+            _ => return Err(format!("unknown argument: {arg}\n{USAGE}")),
+        }
+    }
+    Ok(parsed)
+}
+
+fn parse_cubes(mut args: impl Iterator<Item = String>) -> Result<Cubes, String> {
+    let mut parsed = Cubes {
+        input: "inputs/day_2".to_owned(),
+        bag: None,
+        bag_file: None,
+        at_least: None,
+        report: None,
+    };
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {arg}"))?;
+        match arg.as_str() {
+            "--input" => parsed.input = value,
+            "--bag" => parsed.bag = Some(value),
+            "--bag-file" => parsed.bag_file = Some(value),
+            "--report" => match value.as_str() {
+                "table" => parsed.report = Some(Format::Table),
+                "csv" => parsed.report = Some(Format::Csv),
+                _ => return Err(format!("invalid report format: {value}")),
+            },
+            "--at-least" => {
+                parsed.at_least = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid count: {value}"))?,
+                )
+            }
+            _ => return Err(format!("unknown argument: {arg}\n{USAGE}")),
+        }
+    }
+    if parsed.bag.is_some() && parsed.bag_file.is_some() {
+        return Err("--bag and --bag-file cannot be used together".to_owned());
+    }
+    Ok(parsed)
+}
+
+fn parse_render(mut args: impl Iterator<Item = String>) -> Result<Render, String> {
+    match args.next().as_deref() {
+        Some("10") => (),
+        Some(day) => return Err(format!("day {day} can not be rendered, only day 10 can")),
+        None => return Err(USAGE.to_owned()),
+    }
+    let mut parsed = Render {
+        input: "inputs/day_10".to_owned(),
+        colours: false,
+        svg: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--colour" => parsed.colours = true,
+            "--input" | "--svg" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("missing value for {arg}"))?;
+                match arg.as_str() {
+                    "--input" => parsed.input = value,
+                    _ => parsed.svg = Some(value),
+                }
+            }
+            _ => return Err(format!("unknown argument: {arg}\n{USAGE}")),
+        }
+    }
+    Ok(parsed)
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let days = parse_days(&args.next().ok_or(USAGE)?)?;
+    let mut parsed = Args {
+        days,
+        part: None,
+        input: None,
+    };
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {arg}"))?;
+        match arg.as_str() {
+            "--part" => match value.as_str() {
+                "1" => parsed.part = Some(1),
+                "2" => parsed.part = Some(2),
+                _ => return Err(format!("invalid part: {value}")),
+            },
+            "--input" => parsed.input = Some(value),
+            _ => return Err(format!("unknown argument: {arg}\n{USAGE}")),
+        }
+    }
+    if parsed.input.is_some() && parsed.days.start() != parsed.days.end() {
+        return Err("--input can only be used when running a single day".to_owned());
+    }
+    Ok(parsed)
+}
+
+fn read_input(path: &str) -> std::io::Result<String> {
+    if path == "-" {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        Ok(contents)
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+// Returns whether every line could be decoded
+fn calibrate(args: &Calibrate) -> bool {
+    let reader: Box<dyn std::io::BufRead> = if args.input == "-" {
+        Box::new(std::io::stdin().lock())
+    } else {
+        match std::fs::File::open(&args.input) {
+            Ok(file) => Box::new(std::io::BufReader::new(file)),
+            Err(err) => {
+                eprintln!("day 1: could not read {}: {err}", args.input);
+                return false;
+            }
+        }
+    };
+    let decoder = CalibrationDecoder::new(args.mode);
+    let summary = decoder.decode_stream(reader, |y, value, sum| match value {
+        Ok(value) if args.verbose => println!("line {}: {value} (sum {sum})", y + 1),
+        Ok(_) => (),
+        Err(err) => eprintln!("{err}"),
+    });
+    match summary {
+        Ok(summary) => {
+            println!(
+                "day 1: {} ({} lines, {} without digit)",
+                summary.sum, summary.lines, summary.errors
+            );
+            summary.errors == 0
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            false
+        }
+    }
+}
+
+fn cubes(args: &Cubes) -> Result<(), PuzzleError> {
+    let contents = read_input(&args.input)
+        .map_err(|err| PuzzleError::new(2, format!("could not read {}: {err}", args.input)))?;
+    let games = day2::games(&contents)?;
+    let bag = match (&args.bag, &args.bag_file) {
+        (Some(bag), _) => Bag::parse(bag)?,
+        (None, Some(path)) => Bag::load(path)?,
+        (None, None) => Bag::standard(),
+    };
+    // A report is meant to be read by other tools: it is printed alone
+    if let Some(format) = args.report {
+        print!("{}", day2::report(&games, &bag, format));
+        return Ok(());
+    }
+    let feasible: Vec<_> = bag.feasible(&games).map(|game| game.id).collect();
+    println!("bag: {bag}");
+    println!(
+        "{} feasible games, their ids sum to {}",
+        feasible.len(),
+        feasible.iter().sum::<u32>()
+    );
+    if let Some(k) = args.at_least {
+        match Bag::smallest_for(&games, k) {
+            Some(smallest) => println!(
+                "smallest bag for {k} games: {smallest} ({} cubes)",
+                smallest.total()
+            ),
+            None => println!("there are only {} games", games.len()),
+        }
+    }
+    Ok(())
+}
+
+fn draw(args: &Render) -> Result<(), PuzzleError> {
+    let contents = read_input(&args.input)
+        .map_err(|err| PuzzleError::new(10, format!("could not read {}: {err}", args.input)))?;
+    print!("{}", render::to_text(&contents, args.colours)?);
+    if let Some(path) = &args.svg {
+        std::fs::write(path, render::to_svg(&contents)?)
+            .map_err(|err| PuzzleError::new(10, format!("could not write {path}: {err}")))?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::Calibrate(args)) => std::process::exit(if calibrate(&args) { 0 } else { 1 }),
+        Ok(Command::Cubes(args)) => match cubes(&args) {
+            Ok(()) => std::process::exit(0),
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        },
+        Ok(Command::Render(args)) => match draw(&args) {
+            Ok(()) => std::process::exit(0),
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        },
+        Err(message) => {
+            eprintln!("{message}");
+            std::process::exit(2);
+        }
+    };
+
+    let mut failed = false;
+    for day in args.days.clone() {
+        let Some(solution) = advent2023::solution(day) else {
+            // Asking for a single day that is not solved is an error, a range only warns
+            eprintln!("day {day}: no solution");
+            failed |= args.days.start() == args.days.end();
+            continue;
+        };
+        let path = args
+            .input
+            .clone()
+            .unwrap_or_else(|| format!("inputs/day_{day}"));
+        let contents = match read_input(&path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                eprintln!("day {day}: could not read {path}: {err}");
+                failed = true;
+                continue;
+            }
+        };
+        for part in [1, 2].into_iter().filter(|p| args.part.unwrap_or(*p) == *p) {
+            let answer = if part == 1 {
+                solution.part1(&contents)
+            } else {
+                solution.part2(&contents)
+            };
+            match answer {
+                Ok(answer) => println!("day {day} part {part}: {answer}"),
+                Err(err) => {
+                    eprintln!("{err}");
+                    failed = true;
+                }
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+fn args(args: &str) -> impl Iterator<Item = String> + '_ {
+    args.split_whitespace().map(String::from)
+}
+
+#[test]
+fn test_parse_days() {
+    assert_eq!(Ok(1..=25), parse_days("all"));
+    assert_eq!(Ok(3..=3), parse_days("3"));
+    assert_eq!(Ok(3..=5), parse_days("3-5"));
+    assert!(parse_days("0").is_err());
+    assert!(parse_days("26").is_err());
+    assert!(parse_days("5-3").is_err());
+    assert!(parse_days("1-26").is_err());
+    assert!(parse_days("x").is_err());
+}
+
+#[test]
+fn test_parse_args() {
+    let Ok(Command::Run(run)) = parse_args(args("run 2 --part 1 --input -")) else {
+        panic!("expected a run command");
+    };
+    assert_eq!((2..=2, Some(1)), (run.days, run.part));
+    assert_eq!(Some("-"), run.input.as_deref());
+
+    let error = parse_args(args("run 2-4 --input day_2")).err();
+    assert_eq!(
+        Some("--input can only be used when running a single day"),
+        error.as_deref()
+    );
+    assert!(parse_args(args("run all --input day_2")).is_err());
+    assert!(parse_args(args("run 2 --part 3")).is_err());
+    assert!(parse_args(args("run 2 --part")).is_err());
+    assert!(parse_args(args("run")).is_err());
+    assert!(parse_args(args("fly 2")).is_err());
+
+    let Ok(Command::Cubes(cubes)) = parse_args(args("cubes --at-least 3 --report csv")) else {
+        panic!("expected a cubes command");
+    };
+    assert_eq!((Some(3), Some(Format::Csv)), (cubes.at_least, cubes.report));
+    assert!(parse_args(args("cubes --bag 1_red --bag-file bag")).is_err());
+
+    let Ok(Command::Render(render)) = parse_args(args("render 10 --colour --svg loop.svg")) else {
+        panic!("expected a render command");
+    };
+    assert!(render.colours);
+    assert_eq!(Some("loop.svg"), render.svg.as_deref());
+    assert!(parse_args(args("render 3")).is_err());
+    assert!(parse_args(args("render 10 --svg")).is_err());
+}
@@ -0,0 +1,443 @@
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::PuzzleError;
+
+const DAY: u32 = 1;
+
+const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const FRENCH: [&str; 9] = [
+    "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+const GERMAN: [&str; 9] = [
+    "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+const ORDINALS: [&str; 9] = [
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth",
+];
+const ROMAN: [&str; 9] = ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"];
+
+/// What counts as a digit in a calibration line
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    Digits,         // only 0 to 9, for the first star
+    DigitsAndWords, // also the spelled digits, from "one" to "nine"
+}
+
+/// The words a decoder reads as digits, besides 0 to 9
+/// When a word is listed twice, its first digit is kept
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // The words spelling the digits from 1 to 9
+    fn spelled(words: [&str; 9]) -> Self {
+        Self {
+            words: words.iter().map(|w| w.to_string()).zip(1..).collect(),
+        }
+    }
+
+    pub fn english() -> Self {
+        Self::spelled(ENGLISH)
+    }
+
+    pub fn french() -> Self {
+        Self::spelled(FRENCH)
+    }
+
+    pub fn german() -> Self {
+        Self::spelled(GERMAN)
+    }
+
+    /// From "first" to "ninth"
+    pub fn ordinals() -> Self {
+        Self::spelled(ORDINALS)
+    }
+
+    /// From I to IX
+    pub fn roman() -> Self {
+        Self::spelled(ROMAN)
+    }
+
+    /// Panics if `digit` is not a single digit, as `parse` rejects it
+    pub fn word(mut self, word: impl Into<String>, digit: u32) -> Self {
+        assert!(digit < 10, "{digit} is not a digit from 0 to 9");
+        self.words.push((word.into(), digit));
+        self
+    }
+
+    /// The words of both vocabularies, those of `self` first
+    pub fn extend(mut self, other: Vocabulary) -> Self {
+        self.words.extend(other.words);
+        self
+    }
+
+    /// Reads a table with a word and its digit on each line, like `deux 2`
+    /// Blank lines and those starting with # are ignored
+    pub fn parse(table: &str) -> Result<Self, PuzzleError> {
+        let mut vocabulary = Self::new();
+        for (y, line) in crate::lines(table).filter(|(_, l)| !l.trim_start().starts_with('#')) {
+            let mut fields = line.split_whitespace();
+            let (Some(word), Some(digit), None) = (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(PuzzleError::at(
+                    DAY,
+                    (y, line),
+                    line,
+                    "expected a word and its digit",
+                ));
+            };
+            match digit.parse() {
+                Ok(digit) if digit < 10 => vocabulary = vocabulary.word(word, digit),
+                _ => {
+                    return Err(PuzzleError::at(
+                        DAY,
+                        (y, line),
+                        digit,
+                        "expected a digit from 0 to 9",
+                    ))
+                }
+            }
+        }
+        Ok(vocabulary)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PuzzleError> {
+        let path = path.as_ref();
+        let table = std::fs::read_to_string(path).map_err(|err| {
+            PuzzleError::new(DAY, format!("could not read {}: {err}", path.display()))
+        })?;
+        Self::parse(&table)
+    }
+}
+
+// Aho–Corasick automaton, with every failure link already followed: reading a byte is a
+// single lookup, so a line is scanned in linear time whatever the number of words
+struct Automaton {
+    next: Vec<[usize; 256]>,
+    depth: Vec<usize>, // length of the prefix of a word that the state stands for
+    output: Vec<Option<(usize, u32)>>, // longest word ending in that state: its length and digit
+}
+
+impl Automaton {
+    fn new<'a>(words: impl IntoIterator<Item = (&'a [u8], u32)>) -> Self {
+        // The trie of the words, where 0 is both the root and “no child”
+        let mut next = vec![[0; 256]];
+        let mut depth = vec![0];
+        let mut output = vec![None];
+        for (word, digit) in words {
+            let mut state = 0;
+            for byte in word {
+                if next[state][*byte as usize] == 0 {
+                    next.push([0; 256]);
+                    depth.push(depth[state] + 1);
+                    output.push(None);
+                    next[state][*byte as usize] = next.len() - 1;
+                }
+                state = next[state][*byte as usize];
+            }
+            // With duplicated words, the first one wins
+            output[state] = output[state].or(Some((word.len(), digit)));
+        }
+
+        // Breadth first, the failure of a state is always known before its children
+        let mut failure = vec![0; next.len()];
+        let mut queue: VecDeque<_> = next[0].iter().copied().filter(|s| *s != 0).collect();
+        while let Some(state) = queue.pop_front() {
+            output[state] = output[state].or(output[failure[state]]);
+            let fallbacks = next[failure[state]];
+            for (child, fallback) in next[state].iter_mut().zip(fallbacks) {
+                if *child == 0 {
+                    *child = fallback;
+                } else {
+                    failure[*child] = fallback;
+                    queue.push_back(*child);
+                }
+            }
+        }
+        Self {
+            next,
+            depth,
+            output,
+        }
+    }
+
+    // The word starting first, the longest one if several start at the same byte
+    fn leftmost_longest(&self, text: impl IntoIterator<Item = u8>) -> Option<u32> {
+        let mut state = 0;
+        let mut best: Option<(usize, u32)> = None; // start of the word, and its digit
+        for (i, byte) in text.into_iter().enumerate() {
+            state = self.next[state][byte as usize];
+            if let Some((len, digit)) = self.output[state] {
+                let start = i + 1 - len;
+                // At the same start, a match ending later is longer
+                if best.is_none_or(|(s, _)| start <= s) {
+                    best = Some((start, digit));
+                }
+            }
+            // Nothing being read can start before the best match anymore
+            if best.is_some_and(|(s, _)| i + 1 - self.depth[state] > s) {
+                break;
+            }
+        }
+        best.map(|(_, digit)| digit)
+    }
+}
+
+/// Recovers the calibration value of a line: its first and last digits
+pub struct CalibrationDecoder {
+    forward: Automaton,
+    backward: Automaton, // of the reversed words, to read lines from the end
+}
+
+impl CalibrationDecoder {
+    pub fn new(mode: Mode) -> Self {
+        match mode {
+            Mode::Digits => Self::with_vocabulary(&Vocabulary::new()),
+            Mode::DigitsAndWords => Self::with_vocabulary(&Vocabulary::english()),
+        }
+    }
+
+    pub fn with_vocabulary(vocabulary: &Vocabulary) -> Self {
+        let digits: Vec<_> = ('0'..='9').map(|c| c.to_string()).zip(0..).collect();
+        let words: Vec<_> = digits.iter().chain(&vocabulary.words).collect();
+        let reversed: Vec<(Vec<u8>, u32)> = words
+            .iter()
+            .map(|(word, digit)| (word.bytes().rev().collect(), *digit))
+            .collect();
+        Self {
+            forward: Automaton::new(words.iter().map(|(w, d)| (w.as_bytes(), *d))),
+            backward: Automaton::new(reversed.iter().map(|(w, d)| (&w[..], *d))),
+        }
+    }
+
+    /// The first digit is searched from the start and the last one from the end, so
+    /// overlapping words like "eightwo" count as both 8 and 2
+    /// When several words start (or end, for the last digit) at the same place, the
+    /// longest one is read: "VIII" is 88 and not 18
+    pub fn decode(&self, (y, line): (usize, &str)) -> Result<u32, PuzzleError> {
+        let first = self.forward.leftmost_longest(line.bytes());
+        let last = self.backward.leftmost_longest(line.bytes().rev());
+        match (first, last) {
+            (Some(decimal), Some(unit)) => Ok(decimal * 10 + unit),
+            _ => Err(PuzzleError::at(
+                DAY,
+                (y, line),
+                line,
+                "no digit in the line",
+            )),
+        }
+    }
+
+    /// The sum of the calibration values of all the lines
+    pub fn sum(&self, input: &str) -> Result<u64, PuzzleError> {
+        crate::lines(input)
+            .map(|line| self.decode(line).map(u64::from))
+            .sum()
+    }
+
+    /// Decodes the lines as they are read, keeping only the current one in memory
+    /// `on_line` gets every decoded line with its index and the sum so far; lines without
+    /// digit are reported to it and skipped, only read failures stop the decoding
+    pub fn decode_stream(
+        &self,
+        mut reader: impl BufRead,
+        mut on_line: impl FnMut(usize, &Result<u32, PuzzleError>, u64),
+    ) -> Result<Summary, PuzzleError> {
+        let mut summary = Summary::default();
+        let mut buffer = String::new();
+        for y in 0.. {
+            buffer.clear();
+            match reader.read_line(&mut buffer) {
+                Ok(0) => break,
+                Ok(_) => (),
+                Err(err) => {
+                    return Err(PuzzleError::new(
+                        DAY,
+                        format!("could not read line {}: {err}", y + 1),
+                    ))
+                }
+            }
+            let line = buffer.trim_end_matches(['\n', '\r']);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let value = self.decode((y, line));
+            summary.lines += 1;
+            match value {
+                Ok(value) => summary.sum += u64::from(value),
+                Err(_) => summary.errors += 1,
+            }
+            on_line(y, &value, summary.sum);
+        }
+        Ok(summary)
+    }
+}
+
+/// What a streamed decoding went through
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Summary {
+    pub lines: usize, // not counting blank ones
+    pub errors: usize,
+    pub sum: u64, // of the lines that could be decoded
+}
+
+fn run(input: &str, mode: Mode) -> Result<u64, PuzzleError> {
+    CalibrationDecoder::new(mode).sum(input)
+}
+
+pub struct Day1;
+
+impl crate::Solution for Day1 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run(input, Mode::Digits).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run(input, Mode::DigitsAndWords).map(Into::into)
+    }
+}
+
+#[test]
+fn test_first_star() {
+    let input = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+    assert_eq!(Ok(142), run(input, Mode::Digits))
+}
+
+#[test]
+fn test_input() {
+    let input = r#"two1nine
+    eightwothree
+    abcone2threexyz
+    xtwone3four
+    4nineeightseven2
+    zoneight234
+    7pqrstsixteen"#;
+    assert_eq!(Ok(281), run(input, Mode::DigitsAndWords))
+}
+
+#[test]
+fn test_overlapping_words() {
+    let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+    assert_eq!(Ok(82), decoder.decode((0, "eightwo")));
+    assert_eq!(Ok(21), decoder.decode((0, "twone")));
+    assert_eq!(Ok(88), decoder.decode((0, "xxeightxx")));
+    assert_eq!(Ok(11), decoder.decode((0, "é1ü")));
+
+    // Without words, the line has a single digit
+    let decoder = CalibrationDecoder::new(Mode::Digits);
+    assert_eq!(Ok(33), decoder.decode((0, "twone3four")));
+}
+
+#[test]
+fn test_line_without_digit() {
+    let error = run("two1nine\n\nabcdef\n", Mode::DigitsAndWords).unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((3, 1)));
+    assert!(run("two1nine\nthree\n", Mode::Digits).is_err());
+}
+
+#[test]
+fn test_automaton() {
+    let automaton = Automaton::new([(&b"ab"[..], 1), (b"abcd", 2), (b"bc", 3), (b"c", 4)]);
+    assert_eq!(Some(2), automaton.leftmost_longest(*b"xabcd"));
+    assert_eq!(Some(1), automaton.leftmost_longest(*b"xabce"));
+    assert_eq!(Some(3), automaton.leftmost_longest(*b"xbcab"));
+    assert_eq!(None, automaton.leftmost_longest(*b"xyz"));
+}
+
+#[test]
+fn test_vocabularies() {
+    let decoder = CalibrationDecoder::with_vocabulary(&Vocabulary::french());
+    assert_eq!(Ok(29), decoder.decode((0, "deuxtroisneuf")));
+    let decoder = CalibrationDecoder::with_vocabulary(&Vocabulary::german());
+    assert_eq!(Ok(59), decoder.decode((0, "xfünfyneun")));
+    let decoder = CalibrationDecoder::with_vocabulary(&Vocabulary::ordinals());
+    assert_eq!(Ok(12), decoder.decode((0, "firstandsecond")));
+
+    let vocabulary = Vocabulary::parse("# Spanish\nuno 1\n\ndos 2\n").unwrap();
+    assert_eq!(Vocabulary::new().word("uno", 1).word("dos", 2), vocabulary);
+    let error = Vocabulary::parse("uno 1\ndos two").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 5)));
+    assert!(Vocabulary::parse("uno 1 2").is_err());
+    assert!(Vocabulary::parse("diez 10").is_err());
+    assert!(Vocabulary::load("inputs/no_such_vocabulary").is_err());
+}
+
+#[test]
+#[should_panic(expected = "42 is not a digit from 0 to 9")]
+fn test_word_with_a_number() {
+    let _ = Vocabulary::new().word("x", 42);
+}
+
+#[test]
+fn test_ambiguous_overlaps() {
+    // Roman numerals are prefixes of one another: the longest one is read
+    let decoder = CalibrationDecoder::with_vocabulary(&Vocabulary::roman());
+    assert_eq!(Ok(88), decoder.decode((0, "VIII")));
+    assert_eq!(Ok(44), decoder.decode((0, "xIVx")));
+    assert_eq!(Ok(93), decoder.decode((0, "IXaIII")));
+
+    // "un" ends "neun", which is read as a whole from both ends
+    let vocabulary = Vocabulary::french().extend(Vocabulary::german());
+    let decoder = CalibrationDecoder::with_vocabulary(&vocabulary);
+    assert_eq!(Ok(99), decoder.decode((0, "neun")));
+    assert_eq!(Ok(61), decoder.decode((0, "sixeins")));
+
+    // "seven" starts "seventh", and they agree
+    let vocabulary = Vocabulary::english().extend(Vocabulary::ordinals());
+    let decoder = CalibrationDecoder::with_vocabulary(&vocabulary);
+    assert_eq!(Ok(72), decoder.decode((0, "seventhsecond")));
+    assert_eq!(Ok(98), decoder.decode((0, "ninetheighth")));
+
+    // A word listed twice keeps its first digit
+    let vocabulary = Vocabulary::new().word("ace", 1).word("ace", 9);
+    let decoder = CalibrationDecoder::with_vocabulary(&vocabulary);
+    assert_eq!(Ok(11), decoder.decode((0, "ace")));
+}
+
+#[test]
+fn test_decode_stream() {
+    let input = "two1nine\r\neightwothree\n\nabcdef\n7pqrstsixteen";
+    let decoder = CalibrationDecoder::new(Mode::DigitsAndWords);
+    let mut seen = Vec::new();
+    let summary = decoder
+        .decode_stream(input.as_bytes(), |y, value, sum| {
+            seen.push((y, value.as_ref().ok().copied(), sum))
+        })
+        .unwrap();
+    assert_eq!(
+        vec![
+            (0, Some(29), 29),
+            (1, Some(83), 112),
+            (3, None, 112),
+            (4, Some(76), 188)
+        ],
+        seen
+    );
+    assert_eq!(
+        Summary {
+            lines: 4,
+            errors: 1,
+            sum: 188
+        },
+        summary
+    );
+
+    // Invalid UTF-8 cannot be read
+    let error = decoder
+        .decode_stream(&b"12\n\xff3\n"[..], |_, _, _| ())
+        .unwrap_err();
+    assert!(error.message.starts_with("could not read line 2"));
+}
@@ -0,0 +1,408 @@
+pub mod render;
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::grid::{Direction, Grid, Point};
+use crate::PuzzleError;
+
+const DAY: u32 = 10;
+
+// Where a tile leads to, listed in the order of `Direction::ALL`
+fn pipe(c: char) -> Option<&'static [Direction]> {
+    use Direction::*;
+    match c {
+        '|' => Some(&[Up, Down]),
+        '-' => Some(&[Left, Right]),
+        'L' => Some(&[Up, Right]),
+        'J' => Some(&[Up, Left]),
+        '7' => Some(&[Down, Left]),
+        'F' => Some(&[Down, Right]),
+        '.' => Some(&[]),
+        'S' => Some(&Direction::ALL),
+        _ => None,
+    }
+}
+
+struct Graph {
+    source: Point,
+    grid: Grid<char>,
+}
+
+impl Graph {
+    fn from(input: &str) -> Result<Self, PuzzleError> {
+        let grid = Grid::parse(DAY, input, |c| pipe(c).map(|_| c), "unknown tile")?;
+        let source = grid
+            .iter()
+            .find(|(_, c)| **c == 'S')
+            .map(|(p, _)| p)
+            .ok_or_else(|| PuzzleError::new(DAY, "missing the start tile S"))?;
+        Ok(Self { source, grid })
+    }
+
+    fn valid_succ(&self, n: Point) -> Vec<Point> {
+        pipe(self.grid[n])
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|direction| self.grid.step(n, *direction))
+            .collect()
+    }
+
+    // The neighbours of the node whose pipe leads back to it
+    fn connected(&self, n: Point) -> Vec<Point> {
+        self.valid_succ(n)
+            .into_iter()
+            .filter(|s| self.valid_succ(*s).contains(&n))
+            .collect()
+    }
+
+    /// The tiles of the main loop, in order, starting at S
+    fn find_loop(&self) -> Result<Vec<Point>, PuzzleError> {
+        let starts = self.connected(self.source);
+        if starts.is_empty() {
+            return Err(PuzzleError::new(DAY, "no pipe is connected to S"));
+        }
+        // Dead ends can be connected to S too: only the pipes leading back to it are on the loop
+        starts
+            .into_iter()
+            .find_map(|start| self.follow(start))
+            .ok_or_else(|| PuzzleError::new(DAY, "the loop starting at S is broken"))
+    }
+
+    // The path from S through `start` until it comes back to S, if it does not end before
+    fn follow(&self, start: Point) -> Option<Vec<Point>> {
+        let (mut previous, mut current) = (self.source, start);
+        let mut path = vec![self.source];
+        while current != self.source {
+            path.push(current);
+            let next = self
+                .connected(current)
+                .into_iter()
+                .find(|s| *s != previous)?;
+            previous = current;
+            current = next;
+        }
+        Some(path)
+    }
+
+    /// Number of steps to reach every tile from S, following connected pipes
+    fn distances(&self) -> Grid<Option<u32>> {
+        let mut distances = Grid::filled(self.grid.width(), self.grid.height(), None);
+        distances[self.source] = Some(0);
+        let mut queue = VecDeque::from([(self.source, 0)]);
+        while let Some((node, distance)) = queue.pop_front() {
+            for next in self.connected(node) {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        distances
+    }
+
+    /// The greatest distance from S along the loop, and all the tiles of the loop that far away
+    /// Dead ends connected to S are not part of the loop, however long they are
+    fn farthest(&self) -> Result<(u32, Vec<Point>), PuzzleError> {
+        let distances = self.distances();
+        let path = self.find_loop()?;
+        let max = path.iter().filter_map(|p| distances[*p]).max().unwrap_or(0);
+        let mut tiles: Vec<_> = path
+            .into_iter()
+            .filter(|p| distances[*p] == Some(max))
+            .collect();
+        tiles.sort_by_key(|p| (p.y, p.x));
+        Ok((max, tiles))
+    }
+
+    fn search(&self) -> Result<u32, PuzzleError> {
+        self.farthest().map(|(max, _)| max)
+    }
+
+    /// The corners of the main loop, in the order they are walked from S
+    fn vertices(&self) -> Result<Vec<Point>, PuzzleError> {
+        Ok(corners(&self.find_loop()?))
+    }
+
+    /// Number of tiles enclosed by the loop, computed from its area with the shoelace
+    /// formula and Pick’s theorem: area = inside + boundary / 2 - 1
+    fn enclosed_by_area(&self) -> Result<i64, PuzzleError> {
+        let vertices = self.vertices()?;
+        let (double_area, boundary) = vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(a, b)| {
+                let (ax, ay, bx, by) = (a.x as i64, a.y as i64, b.x as i64, b.y as i64);
+                (ax * by - bx * ay, (ax - bx).abs() + (ay - by).abs())
+            })
+            .fold((0, 0), |(area, length), (a, l)| (area + a, length + l));
+        Ok(double_area.abs() / 2 - boundary / 2 + 1)
+    }
+
+    /// The pipe hidden under S, deduced from the two pipes of the loop on each side of it
+    fn source_pipe(&self) -> Result<char, PuzzleError> {
+        let path = self.find_loop()?;
+        let ends = [path[1], path[path.len() - 1]];
+        let directions: Vec<_> = Direction::ALL
+            .into_iter()
+            .filter(|d| {
+                self.grid
+                    .step(self.source, *d)
+                    .is_some_and(|n| ends.contains(&n))
+            })
+            .collect();
+        "|-LJ7F"
+            .chars()
+            .find(|c| pipe(*c) == Some(&directions[..]))
+            .ok_or_else(|| PuzzleError::new(DAY, "the loop can not go through S"))
+    }
+
+    /// The grid with only the pipes of the loop, S being replaced by its actual pipe
+    fn cleaned(&self) -> Result<Vec<String>, PuzzleError> {
+        let in_loop: HashSet<_> = self.find_loop()?.into_iter().collect();
+        let source_pipe = self.source_pipe()?;
+        let cleaned = Grid::from_fn(self.grid.width(), self.grid.height(), |p| match p {
+            p if p == self.source => source_pipe,
+            p if in_loop.contains(&p) => self.grid[p],
+            _ => '.',
+        });
+        Ok(cleaned.rows().map(|row| row.iter().collect()).collect())
+    }
+
+    /// Every tile of the grid, S being replaced by its actual pipe
+    fn tiles(&self) -> Result<Vec<Vec<Tile>>, PuzzleError> {
+        self.cleaned()?
+            .iter()
+            .enumerate()
+            .map(|(y, line)| classify((y, line)))
+            .collect()
+    }
+
+    /// Number of tiles enclosed by the loop
+    fn enclosed(&self) -> Result<i32, PuzzleError> {
+        self.cleaned()?
+            .iter()
+            .enumerate()
+            .map(|(y, line)| insides((y, line)))
+            .sum()
+    }
+}
+
+// A tile of the path is a corner when its two neighbours are not aligned
+fn corners(path: &[Point]) -> Vec<Point> {
+    let n = path.len();
+    (0..n)
+        .filter(|i| {
+            let (prev, next) = (path[(i + n - 1) % n], path[(i + 1) % n]);
+            prev.x != next.x && prev.y != next.y
+        })
+        .map(|i| path[i])
+        .collect()
+}
+
+/// What a tile of the grid is, once the main loop is known
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Tile {
+    Pipe(char), // Part of the main loop
+    Inside,
+    Outside,
+}
+
+// Scans a line of a cleaned grid, counting how many pipes of the loop were crossed
+fn classify((y, line): (usize, &str)) -> Result<Vec<Tile>, PuzzleError> {
+    let mut outside = true;
+    let mut entered_through = '.';
+    let mut tiles = Vec::with_capacity(line.len());
+    for (x, c) in line.char_indices() {
+        match (c, entered_through) {
+            ('-', _) => (),
+            ('|', _) | ('J', 'F') | ('7', 'L') => {
+                outside = !outside;
+            }
+            ('.', _) => (),
+            ('L', _) => {
+                entered_through = 'L';
+            }
+            ('F', _) => {
+                entered_through = 'F';
+            }
+            ('7', 'F') | ('J', 'L') => (),
+            _ => {
+                return Err(PuzzleError::at(
+                    DAY,
+                    (y, line),
+                    &line[x..],
+                    "unexpected tile in a cleaned loop",
+                ))
+            }
+        }
+        tiles.push(match c {
+            '.' if outside => Tile::Outside,
+            '.' => Tile::Inside,
+            pipe => Tile::Pipe(pipe),
+        });
+    }
+
+    Ok(tiles)
+}
+
+fn insides(line: (usize, &str)) -> Result<i32, PuzzleError> {
+    Ok(classify(line)?
+        .into_iter()
+        .filter(|tile| *tile == Tile::Inside)
+        .count() as i32)
+}
+
+fn run2(input: &str) -> Result<i32, PuzzleError> {
+    let graph = Graph::from(input)?;
+    let enclosed = graph.enclosed()?;
+    debug_assert_eq!(Ok(enclosed as i64), graph.enclosed_by_area());
+    Ok(enclosed)
+}
+
+pub struct Day10;
+
+impl crate::Solution for Day10 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        Graph::from(input)?.search().map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run2(input).map(Into::into)
+    }
+}
+
+#[test]
+fn test_simple() {
+    let input = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+
+    let g = Graph::from(input).unwrap();
+    assert_eq!(Ok(4), g.search());
+}
+
+#[test]
+fn run_complex() {
+    let input = "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+    let g = Graph::from(input).unwrap();
+    assert_eq!(Ok(8), g.search());
+}
+
+#[test]
+fn simple2() {
+    let input = "...........
+.F-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+    let cleaned: Result<i32, _> = crate::lines(input).map(insides).sum();
+    assert_eq!(Ok(4), cleaned);
+
+    let input = input.replacen('F', "S", 1);
+    assert_eq!(Ok('F'), Graph::from(&input).unwrap().source_pipe());
+    assert_eq!(Ok(4), run2(&input));
+}
+
+#[test]
+fn test_enclosed_with_junk_pipes() {
+    let input = ".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+    assert_eq!(Ok(8), run2(input));
+
+    let input = "FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L";
+    let g = Graph::from(input).unwrap();
+    assert_eq!(Ok('7'), g.source_pipe());
+    assert_eq!(Ok(10), g.enclosed());
+    assert_eq!(Ok(10), g.enclosed_by_area());
+}
+
+#[test]
+fn test_loop_vertices() {
+    let input = ".....
+.S-7.
+.|.|.
+.L-J.
+.....";
+    let g = Graph::from(input).unwrap();
+    let corners = [(1, 1), (3, 1), (3, 3), (1, 3)].map(|(x, y)| Point::new(x, y));
+    let vertices = g.vertices().unwrap();
+    assert_eq!(4, vertices.len());
+    assert!(corners.iter().all(|c| vertices.contains(c)));
+    assert_eq!(Ok(1), g.enclosed_by_area());
+}
+
+#[test]
+fn test_invalid_grid() {
+    let error = Graph::from(".....\n.S-7.\n.|x|.\n").err().unwrap();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((3, 3)));
+    assert!(Graph::from(".....\n.S-7.\n.|.|\n").is_err());
+    assert!(Graph::from(".....\n..-7.\n").is_err());
+    assert!(Graph::from("...\n.S.\n...").unwrap().search().is_err());
+}
+
+#[test]
+fn test_distances_with_dead_ends() {
+    // A dead end on the left of S, and pipes leading into the side of the loop
+    let input = "......
+-S-7..
+.|-|-.
+.L-J|.";
+    let g = Graph::from(input).unwrap();
+    let distances = g.distances();
+    assert_eq!(Some(1), distances[Point::new(0, 1)]);
+    assert_eq!(Some(4), distances[Point::new(3, 3)]);
+    assert_eq!(None, distances[Point::new(2, 2)]);
+    assert_eq!(None, distances[Point::new(4, 3)]);
+    assert_eq!(Ok((4, vec![Point::new(3, 3)])), g.farthest());
+    assert_eq!(Ok('F'), g.source_pipe());
+    // The junk pipe in the middle of the loop is enclosed
+    assert_eq!(Ok(1), g.enclosed());
+
+    // The dead end comes first among the pipes connected to S
+    let input = "-S-7.\n.|.|.\n.L-J.";
+    assert_eq!(
+        Ok(8),
+        Graph::from(input).unwrap().find_loop().map(|l| l.len())
+    );
+    assert_eq!(Ok(1), run2(input));
+    assert!(render::to_text(input, false).is_ok());
+    assert!(render::to_svg(input).is_ok());
+
+    // A dead end longer than half of the loop
+    let input = "------S-7\n......|.|\n......L-J\n";
+    let g = Graph::from(input).unwrap();
+    assert_eq!(Some(6), g.distances()[Point::new(0, 0)]);
+    assert_eq!(Ok(4), g.search());
+
+    // The pipe below the start is broken: there is no loop
+    let g = Graph::from("S-7\n|.|\nL-.").unwrap();
+    assert!(g.farthest().is_err());
+    assert!(g.search().is_err());
+}
@@ -0,0 +1,171 @@
+use std::fmt::Write;
+
+use super::{Graph, Tile};
+use crate::grid::Point;
+use crate::PuzzleError;
+
+// Size, in SVG units, of a tile
+const TILE_SIZE: usize = 10;
+
+const RESET: &str = "\x1b[0m";
+const LOOP_COLOUR: &str = "\x1b[1;33m";
+const INSIDE_COLOUR: &str = "\x1b[42m";
+const OUTSIDE_COLOUR: &str = "\x1b[2m";
+
+fn box_drawing(c: char) -> char {
+    match c {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        other => other,
+    }
+}
+
+/// The grid drawn with box-drawing characters
+/// With `colours`, the loop, the inside and the outside are highlighted with ANSI escape codes
+pub fn to_text(input: &str, colours: bool) -> Result<String, PuzzleError> {
+    let graph = Graph::from(input)?;
+    let mut text = String::new();
+    for (tiles, row) in graph.tiles()?.iter().zip(graph.grid.rows()) {
+        for (tile, c) in tiles.iter().zip(row) {
+            // Pipes that are not part of the loop are still drawn, but as the tiles they are
+            let (colour, c) = match tile {
+                Tile::Pipe(pipe) => (LOOP_COLOUR, *pipe),
+                Tile::Inside => (INSIDE_COLOUR, *c),
+                Tile::Outside => (OUTSIDE_COLOUR, *c),
+            };
+            if colours {
+                text.push_str(colour);
+                text.push(box_drawing(c));
+                text.push_str(RESET);
+            } else {
+                text.push(box_drawing(c));
+            }
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+fn digit(distance: u32) -> char {
+    char::from_digit(distance % 10, 10).unwrap_or('?')
+}
+
+/// The distance from S of every tile reached, modulo 10, the farthest tiles of the loop
+/// being highlighted
+pub fn distances_to_text(input: &str, colours: bool) -> Result<String, PuzzleError> {
+    let graph = Graph::from(input)?;
+    let distances = graph.distances();
+    let (_, farthest) = graph.farthest()?;
+    let mut text = String::new();
+    for (y, row) in distances.rows().enumerate() {
+        for (x, distance) in row.iter().enumerate() {
+            let (colour, c) = match distance {
+                Some(d) if farthest.contains(&Point::new(x, y)) => (LOOP_COLOUR, digit(*d)),
+                Some(d) => (RESET, digit(*d)),
+                None => (OUTSIDE_COLOUR, '.'),
+            };
+            if colours {
+                text.push_str(colour);
+                text.push(c);
+                text.push_str(RESET);
+            } else {
+                text.push(c);
+            }
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// An SVG image of the grid: inside tiles are filled, and the loop is drawn as a polygon
+pub fn to_svg(input: &str) -> Result<String, PuzzleError> {
+    let graph = Graph::from(input)?;
+    let tiles = graph.tiles()?;
+    let height = tiles.len() * TILE_SIZE;
+    let width = tiles.first().map_or(0, Vec::len) * TILE_SIZE;
+    let centre = |n: usize| n * TILE_SIZE + TILE_SIZE / 2;
+
+    // Writing to a String cannot fail
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"  <rect width="{width}" height="{height}" fill="white"/>"#
+    );
+    for (y, row) in tiles.iter().enumerate() {
+        for (x, _) in row.iter().enumerate().filter(|(_, t)| **t == Tile::Inside) {
+            let _ = writeln!(
+                svg,
+                r#"  <rect x="{}" y="{}" width="{TILE_SIZE}" height="{TILE_SIZE}" fill="lightgreen"/>"#,
+                x * TILE_SIZE,
+                y * TILE_SIZE
+            );
+        }
+    }
+    let points: Vec<_> = graph
+        .vertices()?
+        .iter()
+        .map(|n| format!("{},{}", centre(n.x), centre(n.y)))
+        .collect();
+    let _ = writeln!(
+        svg,
+        r#"  <polygon points="{}" fill="none" stroke="darkorange" stroke-width="2"/>"#,
+        points.join(" ")
+    );
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........";
+
+#[test]
+fn test_to_text() {
+    let text = to_text(EXAMPLE, false).unwrap();
+    assert_eq!(text.lines().nth(1), Some(".┌──────┐."));
+    assert_eq!(text.lines().nth(6), Some(".│..││..│."));
+    assert_eq!(text.lines().count(), 9);
+
+    let coloured = to_text(EXAMPLE, true).unwrap();
+    assert_eq!(coloured.matches(INSIDE_COLOUR).count(), 4);
+    assert_eq!(coloured.matches(LOOP_COLOUR).count(), 44);
+}
+
+#[test]
+fn test_junk_pipes_are_not_highlighted() {
+    let text = to_text("-L|F7\n7S-7|\nL|7||\n-L-J|\nL|-JF", true).unwrap();
+    assert_eq!(text.matches(LOOP_COLOUR).count(), 8);
+    assert!(text.starts_with(&format!("{OUTSIDE_COLOUR}─{RESET}")));
+}
+
+#[test]
+fn test_distances_to_text() {
+    let text = distances_to_text("-S-7.\n.|.|.\n.L-J.", false).unwrap();
+    assert_eq!("1012.\n.1.3.\n.234.\n", text);
+    let coloured = distances_to_text("-S-7.\n.|.|.\n.L-J.", true).unwrap();
+    assert!(coloured.contains(&format!("{LOOP_COLOUR}4{RESET}")));
+}
+
+#[test]
+fn test_to_svg() {
+    let svg = to_svg(EXAMPLE).unwrap();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("lightgreen").count(), 4);
+    assert!(svg.contains(r#"<polygon points="15,15 15,75 45,75 45,55 "#));
+}
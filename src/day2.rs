@@ -0,0 +1,666 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{char, line_ending, multispace0, space0, space1, u32};
+use nom::combinator::{cut, eof, map};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+use nom::IResult;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::PuzzleError;
+
+const DAY: u32 = 2;
+
+/// Cubes of a single colour, shown together in a draw
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cubes {
+    pub count: u32,
+    pub colour: String,
+}
+
+/// A handful of cubes taken from the bag and shown by the elf
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Draw {
+    pub cubes: Vec<Cubes>,
+}
+
+impl Draw {
+    /// How many cubes of that colour were shown, 0 if there were none
+    pub fn count(&self, colour: &str) -> u32 {
+        self.cubes
+            .iter()
+            .filter(|c| c.colour == colour)
+            .map(|c| c.count)
+            .sum()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Game {
+    pub id: u32,
+    pub draws: Vec<Draw>,
+}
+
+// Colours are made of letters of any alphabet, possibly joined with - or _
+fn colour(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_alphabetic() || c == '-' || c == '_')(input)
+}
+
+fn cubes(input: &str) -> IResult<&str, Cubes> {
+    map(
+        separated_pair(u32, space1, colour),
+        |(count, colour): (u32, &str)| Cubes {
+            count,
+            colour: colour.to_owned(),
+        },
+    )(input)
+}
+
+// Once a separator is read, what follows must be valid: errors point at it
+fn draw(input: &str) -> IResult<&str, Draw> {
+    map(
+        separated_list1(pair(char(','), space0), cut(cubes)),
+        |cubes| Draw { cubes },
+    )(input)
+}
+
+fn game(input: &str) -> IResult<&str, Game> {
+    let id = delimited(pair(tag("Game"), space1), u32, pair(char(':'), space0));
+    let draws = separated_list1(pair(char(';'), space0), cut(draw));
+    map(
+        terminated(tuple((id, draws)), preceded(space0, eof)),
+        |(id, draws)| Game { id, draws },
+    )(input)
+}
+
+impl Game {
+    pub fn from((y, line): (usize, &str)) -> Result<Self, PuzzleError> {
+        game(line).map(|(_, game)| game).map_err(|err| match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                PuzzleError::at(DAY, (y, line), e.input, "malformed game")
+            }
+            nom::Err::Incomplete(_) => PuzzleError::new(DAY, "the game is incomplete"),
+        })
+    }
+
+    /// The most cubes of that colour shown in a single draw
+    pub fn max(&self, colour: &str) -> u32 {
+        self.draws
+            .iter()
+            .map(|d| d.count(colour))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Every game of the record, one per line
+pub fn games(input: &str) -> Result<Vec<Game>, PuzzleError> {
+    crate::lines(input).map(Game::from).collect()
+}
+
+// Cubes separated by commas or new lines, as in `12 red, 13 green` or a file with one per line
+fn bag(input: &str) -> IResult<&str, Vec<Cubes>> {
+    let separator = tuple((space0, alt((tag(","), line_ending)), multispace0));
+    delimited(
+        multispace0,
+        separated_list1(separator, cut(cubes)),
+        pair(multispace0, eof),
+    )(input)
+}
+
+/// The cubes the elf may have put in the bag, by colour
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Bag {
+    cubes: BTreeMap<String, u32>,
+}
+
+impl Bag {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bag of the first star: 12 red cubes, 13 green cubes, and 14 blue cubes
+    pub fn standard() -> Self {
+        Self::new()
+            .with("red", 12)
+            .with("green", 13)
+            .with("blue", 14)
+    }
+
+    pub fn with(mut self, colour: impl Into<String>, count: u32) -> Self {
+        self.cubes.insert(colour.into(), count);
+        self
+    }
+
+    /// Reads a bag written like a draw: `12 red, 13 green, 14 blue`
+    /// The cubes can also be separated by new lines, for configuration files
+    pub fn parse(text: &str) -> Result<Self, PuzzleError> {
+        let (_, cubes) = bag(text.trim_end()).map_err(|err| match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                PuzzleError::in_input(DAY, text, e.input, "malformed bag")
+            }
+            nom::Err::Incomplete(_) => PuzzleError::new(DAY, "the bag is incomplete"),
+        })?;
+        let mut bag = Self::new();
+        for Cubes { count, colour } in cubes {
+            if bag.cubes.insert(colour.clone(), count).is_some() {
+                return Err(PuzzleError::new(
+                    DAY,
+                    format!("{colour} cubes are listed twice in the bag"),
+                ));
+            }
+        }
+        Ok(bag)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PuzzleError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|err| {
+            PuzzleError::new(DAY, format!("could not read {}: {err}", path.display()))
+        })?;
+        Self::parse(&text)
+    }
+
+    /// The fewest cubes of each colour that could have made the game possible
+    pub fn minimal(game: &Game) -> Self {
+        let mut bag = Self::new();
+        for cubes in game.draws.iter().flat_map(|d| &d.cubes) {
+            let max = game.max(&cubes.colour);
+            bag.cubes.insert(cubes.colour.clone(), max);
+        }
+        bag
+    }
+
+    /// Number of cubes of that colour, 0 when the colour is not in the bag
+    pub fn count(&self, colour: &str) -> u32 {
+        self.cubes.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes
+            .iter()
+            .map(|(colour, count)| (colour.as_str(), *count))
+    }
+
+    pub fn total(&self) -> u32 {
+        self.cubes.values().sum()
+    }
+
+    /// The product of the number of cubes of each colour of the bag
+    pub fn power(&self) -> u64 {
+        self.cubes.values().map(|c| u64::from(*c)).product()
+    }
+
+    /// Whether every draw of the game could have been taken from this bag
+    pub fn allows(&self, game: &Game) -> bool {
+        self.violations(game).next().is_none()
+    }
+
+    /// Every draw showing more cubes of a colour than the bag holds, in the order of the game
+    pub fn violations<'a>(&'a self, game: &'a Game) -> impl Iterator<Item = Violation> + 'a {
+        game.draws.iter().enumerate().flat_map(move |(i, draw)| {
+            // A colour shown twice in a draw is only reported once
+            let colours = draw
+                .cubes
+                .iter()
+                .enumerate()
+                .filter(|(j, c)| draw.cubes[..*j].iter().all(|d| d.colour != c.colour))
+                .map(|(_, c)| c.colour.as_str());
+            colours.filter_map(move |colour| {
+                let (shown, available) = (draw.count(colour), self.count(colour));
+                (shown > available).then(|| Violation {
+                    draw: i,
+                    colour: colour.to_owned(),
+                    shown,
+                    available,
+                })
+            })
+        })
+    }
+
+    /// The games that could have been played with this bag
+    pub fn feasible<'a>(&'a self, games: &'a [Game]) -> impl Iterator<Item = &'a Game> {
+        games.iter().filter(|game| self.allows(game))
+    }
+
+    /// The bag with the fewest cubes that makes at least `k` games possible, if there are that many
+    /// Combinations of the maxima seen in the games are tried but for the last colour,
+    /// whose count is then the `k`-th smallest maximum of the games still possible.
+    /// A combination is dropped as soon as it has as many cubes as the best bag found
+    pub fn smallest_for(games: &[Game], k: usize) -> Option<Self> {
+        if k > games.len() {
+            return None;
+        }
+        if k == 0 {
+            // No game has to be possible
+            return Some(Self::new());
+        }
+        let mut colours: Vec<&str> = games
+            .iter()
+            .flat_map(|g| g.draws.iter().flat_map(|d| &d.cubes))
+            .map(|c| c.colour.as_str())
+            .collect();
+        colours.sort_unstable();
+        colours.dedup();
+        let games: Vec<&Game> = games.iter().collect();
+        let mut best = None;
+        smallest_for(&games, k, &colours, Self::new(), &mut best);
+        best
+    }
+}
+
+/// A draw that could not have been taken from the bag
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Violation {
+    pub draw: usize, // index in the draws of the game
+    pub colour: String,
+    pub shown: u32,
+    pub available: u32,
+}
+
+impl Violation {
+    /// How many cubes were missing in the bag
+    pub fn excess(&self) -> u32 {
+        self.shown - self.available
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "draw {} shows {} {} but the bag has {} (+{})",
+            self.draw + 1,
+            self.shown,
+            self.colour,
+            self.available,
+            self.excess()
+        )
+    }
+}
+
+/// What was seen in a game, or in several of them
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Statistics {
+    pub games: usize,
+    pub draws: usize,
+    pub cubes: BTreeMap<String, u32>, // total of the cubes shown, by colour
+}
+
+impl Statistics {
+    pub fn of(game: &Game) -> Self {
+        let mut statistics = Self {
+            games: 1,
+            draws: game.draws.len(),
+            ..Self::default()
+        };
+        for cubes in game.draws.iter().flat_map(|d| &d.cubes) {
+            *statistics.cubes.entry(cubes.colour.clone()).or_default() += cubes.count;
+        }
+        statistics
+    }
+
+    pub fn of_all(games: &[Game]) -> Self {
+        let mut total = Self::default();
+        for statistics in games.iter().map(Self::of) {
+            total.games += statistics.games;
+            total.draws += statistics.draws;
+            for (colour, count) in statistics.cubes {
+                *total.cubes.entry(colour).or_default() += count;
+            }
+        }
+        total
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Table, // aligned columns, ending with the totals
+    Csv,
+}
+
+/// One line per game: its statistics, and the first draw that the bag could not allow
+pub fn report(games: &[Game], bag: &Bag, format: Format) -> String {
+    let total = Statistics::of_all(games);
+    let colours: Vec<&String> = total.cubes.keys().collect();
+    let mut header: Vec<String> = vec!["game".into(), "draws".into()];
+    header.extend(colours.iter().map(|c| c.to_string()));
+    header.extend(["feasible", "draw", "colour", "shown", "available", "excess"].map(String::from));
+
+    let mut rows = vec![header];
+    for game in games {
+        let statistics = Statistics::of(game);
+        let mut row = vec![game.id.to_string(), statistics.draws.to_string()];
+        row.extend(
+            colours
+                .iter()
+                .map(|c| statistics.cubes.get(*c).copied().unwrap_or(0).to_string()),
+        );
+        match bag.violations(game).next() {
+            None => row.extend(["yes", "", "", "", "", ""].map(String::from)),
+            Some(v) => row.extend([
+                "no".to_owned(),
+                (v.draw + 1).to_string(),
+                v.colour.clone(),
+                v.shown.to_string(),
+                v.available.to_string(),
+                v.excess().to_string(),
+            ]),
+        }
+        rows.push(row);
+    }
+
+    match format {
+        Format::Csv => rows.iter().map(|row| row.join(",") + "\n").collect(),
+        Format::Table => {
+            let feasible = games.iter().filter(|g| bag.allows(g)).count();
+            let mut row = vec!["total".to_owned(), total.draws.to_string()];
+            row.extend(colours.iter().map(|c| total.cubes[*c].to_string()));
+            row.push(format!("{feasible}/{}", total.games));
+            rows.push(row);
+            let widths: Vec<usize> = (0..rows[0].len())
+                .map(|i| {
+                    rows.iter()
+                        .filter_map(|r| r.get(i))
+                        .map(String::len)
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+            rows.iter()
+                .map(|row| {
+                    let cells: Vec<_> = row
+                        .iter()
+                        .zip(&widths)
+                        .enumerate()
+                        // Game ids on the left, numbers on the right
+                        .map(|(i, (cell, width))| match i {
+                            0 => format!("{cell:<width$}"),
+                            _ => format!("{cell:>width$}"),
+                        })
+                        .collect();
+                    cells.join("  ").trim_end().to_owned() + "\n"
+                })
+                .collect()
+        }
+    }
+}
+
+// Written like a draw, so that it can be parsed back
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cubes: Vec<_> = self
+            .colours()
+            .map(|(colour, count)| format!("{count} {colour}"))
+            .collect();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+// Tries every count of the first colour for which at least `k` of the games are still possible
+fn smallest_for(games: &[&Game], k: usize, colours: &[&str], bag: Bag, best: &mut Option<Bag>) {
+    let worse = |total: u32, best: &Option<Bag>| best.as_ref().is_some_and(|b| total >= b.total());
+    if worse(bag.total(), best) {
+        return;
+    }
+    let Some((colour, others)) = colours.split_first() else {
+        // No colour at all: nothing is needed
+        *best = Some(bag);
+        return;
+    };
+    let mut maxima: Vec<u32> = games.iter().map(|g| g.max(colour)).collect();
+    maxima.sort_unstable();
+    if others.is_empty() {
+        let bag = bag.with(*colour, maxima[k - 1]);
+        if best.as_ref().is_none_or(|b| bag.total() < b.total()) {
+            *best = Some(bag);
+        }
+        return;
+    }
+    maxima.dedup();
+    for count in maxima {
+        // The maxima are sorted: the next counts can only be worse
+        if worse(bag.total() + count, best) {
+            break;
+        }
+        let possible: Vec<&Game> = games
+            .iter()
+            .copied()
+            .filter(|g| g.max(colour) <= count)
+            .collect();
+        if possible.len() >= k {
+            let bag = bag.clone().with(*colour, count);
+            smallest_for(&possible, k, others, bag, best);
+        }
+    }
+}
+
+fn run1(input: &str) -> Result<u32, PuzzleError> {
+    Ok(Bag::standard()
+        .feasible(&games(input)?)
+        .map(|game| game.id)
+        .sum())
+}
+
+fn run2(input: &str) -> Result<u64, PuzzleError> {
+    // A colour never drawn in a game makes its power 0
+    Ok(games(input)?
+        .iter()
+        .map(Bag::minimal)
+        .map(|bag| {
+            ["red", "green", "blue"]
+                .map(|c| u64::from(bag.count(c)))
+                .iter()
+                .product::<u64>()
+        })
+        .sum())
+}
+
+pub struct Day2;
+
+impl crate::Solution for Day2 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run1(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run2(input).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+#[test]
+fn test_star1() {
+    assert_eq!(Ok(8), run1(EXAMPLE))
+}
+
+#[test]
+fn test_star2() {
+    assert_eq!(Ok(2286), run2(EXAMPLE))
+}
+
+#[test]
+fn test_invalid_game() {
+    let error = run1("Game 1: 3 blue\nGame two: 1 red\n").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 6)));
+    let error = run1("Game 1: 3 blue, 4 red; 1 red, x green").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((1, 31)));
+    let error = run1("Game 1: 3 blue; 4 red;").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((1, 23)));
+    assert!(run1("Game 1: 3 blue 4 red").is_err());
+}
+
+#[test]
+fn test_parse_game() {
+    let game = Game::from((0, "Game 12: 3 blue, 4 red; 1 teal, 2 red, 2 teal")).unwrap();
+    assert_eq!(12, game.id);
+    assert_eq!(2, game.draws.len());
+    assert_eq!(
+        Cubes {
+            count: 1,
+            colour: "teal".to_owned()
+        },
+        game.draws[1].cubes[0]
+    );
+    assert_eq!(3, game.draws[1].count("teal"));
+    assert_eq!(4, game.max("red"));
+    assert_eq!(0, game.max("green"));
+
+    let game = Game::from((0, "Game 1: 3 grün, 1 light-blue; 2 蓝")).unwrap();
+    assert_eq!(3, game.max("grün"));
+    assert_eq!(1, game.max("light-blue"));
+    assert_eq!(2, game.max("蓝"));
+    assert_eq!(Ok(Bag::new().with("grün", 3)), Bag::parse("3 grün"));
+}
+
+#[test]
+fn test_parse_bag() {
+    assert_eq!(Ok(Bag::standard()), Bag::parse("12 red, 13 green, 14 blue"));
+    assert_eq!(
+        Ok(Bag::standard()),
+        Bag::parse("12 red\n13 green,\n14 blue\n")
+    );
+    assert_eq!(
+        Ok(Bag::standard()),
+        Bag::parse("12 red\r\n13 green\r\n14 blue\r\n")
+    );
+    let error = Bag::parse("12 red\r\nmany green\r\n").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 1)));
+    assert_eq!(Ok(Bag::new().with("teal", 3)), Bag::parse("3 teal"));
+    let error = Bag::parse("12 red\n13 green\nmany blue").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((3, 1)));
+    assert!(Bag::parse("12 red, 3 red").is_err());
+    assert_eq!("14 blue, 13 green, 12 red", Bag::standard().to_string());
+    assert!(Bag::parse("").is_err());
+}
+
+#[test]
+fn test_bag_queries() {
+    let games = games(EXAMPLE).unwrap();
+    let feasible: Vec<_> = Bag::standard().feasible(&games).map(|g| g.id).collect();
+    assert_eq!(vec![1, 2, 5], feasible);
+
+    let minimal = Bag::minimal(&games[0]);
+    assert_eq!(
+        Bag::new().with("red", 4).with("green", 2).with("blue", 6),
+        minimal
+    );
+    assert_eq!(48, minimal.power());
+    assert!(minimal.allows(&games[0]));
+    assert!(!minimal.allows(&games[2]));
+
+    // Games 1, 2 and 5 need 6 blue cubes, and 5 is the only one needing 6 red
+    let smallest = Bag::smallest_for(&games, 3).unwrap();
+    assert_eq!(
+        Bag::new().with("red", 6).with("green", 3).with("blue", 6),
+        smallest
+    );
+    assert_eq!(3, smallest.feasible(&games).count());
+    assert_eq!(
+        Some(Bag::new().with("red", 1).with("green", 3).with("blue", 4)),
+        Bag::smallest_for(&games, 1)
+    );
+    assert_eq!(
+        Some(
+            Bag::new()
+                .with("red", 20)
+                .with("green", 13)
+                .with("blue", 15)
+        ),
+        Bag::smallest_for(&games, 5)
+    );
+    assert_eq!(None, Bag::smallest_for(&games, 6));
+    assert_eq!(Some(Bag::new()), Bag::smallest_for(&games, 0));
+    assert_eq!(Some(Bag::new()), Bag::smallest_for(&[], 0));
+}
+
+#[test]
+fn test_violations() {
+    let games = games(EXAMPLE).unwrap();
+    let bag = Bag::standard();
+    let violations: Vec<_> = bag.violations(&games[3]).collect();
+    assert_eq!(
+        vec![
+            Violation {
+                draw: 2,
+                colour: "blue".to_owned(),
+                shown: 15,
+                available: 14
+            },
+            Violation {
+                draw: 2,
+                colour: "red".to_owned(),
+                shown: 14,
+                available: 12
+            }
+        ],
+        violations
+    );
+    assert_eq!(
+        "draw 3 shows 15 blue but the bag has 14 (+1)",
+        violations[0].to_string()
+    );
+    assert_eq!(0, bag.violations(&games[0]).count());
+    let game = Game::from((0, "Game 7: 1 red, 2 blue, 3 red; 1 red")).unwrap();
+    let violations: Vec<_> = Bag::new().with("red", 3).violations(&game).collect();
+    assert_eq!(2, violations.len());
+    assert_eq!(
+        (0, "red", 4),
+        (
+            violations[0].draw,
+            violations[0].colour.as_str(),
+            violations[0].shown
+        )
+    );
+    assert_eq!(
+        (0, "blue"),
+        (violations[1].draw, violations[1].colour.as_str())
+    );
+}
+
+#[test]
+fn test_statistics() {
+    let games = games(EXAMPLE).unwrap();
+    let statistics = Statistics::of(&games[0]);
+    assert_eq!(3, statistics.draws);
+    assert_eq!(Some(&9), statistics.cubes.get("blue"));
+    let total = Statistics::of_all(&games);
+    assert_eq!((5, 14), (total.games, total.draws));
+    assert_eq!(Some(&61), total.cubes.get("red"));
+}
+
+#[test]
+fn test_report() {
+    let games = games(EXAMPLE).unwrap();
+    let csv = report(&games, &Bag::standard(), Format::Csv);
+    assert_eq!(
+        "game,draws,blue,green,red,feasible,draw,colour,shown,available,excess
+1,3,9,4,5,yes,,,,,
+2,3,6,6,1,yes,,,,,
+3,3,11,26,25,no,1,red,20,12,8
+4,3,21,7,23,no,3,blue,15,14,1
+5,2,3,5,7,yes,,,,,
+",
+        csv
+    );
+    let table = report(&games, &Bag::standard(), Format::Table);
+    assert_eq!(
+        "game   draws  blue  green  red  feasible  draw  colour  shown  available  excess
+1          3     9      4    5       yes
+2          3     6      6    1       yes
+3          3    11     26   25        no     1     red     20         12       8
+4          3    21      7   23        no     3    blue     15         14       1
+5          2     3      5    7       yes
+total     14    50     48   61       3/5
+",
+        table
+    );
+}
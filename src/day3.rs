@@ -0,0 +1,425 @@
+use std::collections::BTreeMap;
+
+use crate::grid::{Grid, Point};
+use crate::PuzzleError;
+
+const DAY: u32 = 3;
+
+/// A symbol of the engine schematic and where it is
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Symbol {
+    pub position: Point,
+    pub value: char,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct Number {
+    x_min: usize, // position of leftest digit
+    x_max: usize, // position of the rightest digit
+    y: usize,
+    value: i32,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+enum Token {
+    Number(Number),
+    Symbol(Symbol),
+}
+
+/// Splits a line into numbers and symbols, in a single pass over its bytes
+/// Positions are counted in characters, so that a multi-byte symbol takes a single cell
+struct Tokenizer<'a> {
+    y: usize,
+    line: &'a str,
+    offset: usize, // in bytes
+    x: usize,      // in characters
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new((y, line): (usize, &'a str)) -> Self {
+        Self {
+            y,
+            line,
+            offset: 0,
+            x: 0,
+        }
+    }
+
+    fn error(&self, offset: usize, message: &str) -> PuzzleError {
+        PuzzleError::at(DAY, (self.y, self.line), &self.line[offset..], message)
+    }
+
+    fn number(&mut self) -> Result<Number, PuzzleError> {
+        let (start, x_min) = (self.offset, self.x);
+        let mut value: i32 = 0;
+        while let Some(digit @ b'0'..=b'9') = self.line.as_bytes().get(self.offset) {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(i32::from(digit - b'0')))
+                .ok_or_else(|| self.error(start, "number too large"))?;
+            self.offset += 1;
+            self.x += 1;
+        }
+        Ok(Number {
+            x_min,
+            x_max: self.x - 1,
+            y: self.y,
+            value,
+        })
+    }
+}
+
+impl Iterator for Tokenizer<'_> {
+    type Item = Result<Token, PuzzleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let byte = *self.line.as_bytes().get(self.offset)?;
+            match byte {
+                b'.' => {
+                    self.offset += 1;
+                    self.x += 1;
+                }
+                b'0'..=b'9' => return Some(self.number().map(Token::Number)),
+                _ => {
+                    // Only decode the character when it is not plain ASCII
+                    let c = if byte.is_ascii() {
+                        char::from(byte)
+                    } else {
+                        self.line[self.offset..].chars().next()?
+                    };
+                    if c.is_alphanumeric() || c.is_whitespace() || c.is_control() {
+                        return Some(Err(self.error(self.offset, "unexpected character")));
+                    }
+                    let symbol = Symbol {
+                        position: Point::new(self.x, self.y),
+                        value: c,
+                    };
+                    self.offset += c.len_utf8();
+                    self.x += 1;
+                    return Some(Ok(Token::Symbol(symbol)));
+                }
+            }
+        }
+    }
+}
+
+/// How many parts must touch a symbol for it to be taken into account
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Adjacency {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Adjacency {
+    fn matches(self, parts: usize) -> bool {
+        match self {
+            Adjacency::Exactly(k) => parts == k,
+            Adjacency::AtLeast(k) => parts >= k,
+        }
+    }
+}
+
+/// How the symbols of a kind are surrounded by parts
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SymbolStatistics {
+    pub occurrences: usize,
+    pub parts: usize, // parts touching any of the symbols, counted once per symbol
+    pub max_parts: usize, // the most parts touching a single symbol
+}
+
+/// The numbers and symbols of an engine schematic
+/// Every cell knows which number covers it, so finding the numbers around a symbol
+/// only looks at its eight neighbours
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    number_at: Grid<Option<usize>>, // index in `numbers` of the number covering the cell
+}
+
+impl Schematic {
+    pub fn from(input: &str) -> Result<Self, PuzzleError> {
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        let (mut width, mut height) = (0, 0);
+        for line in crate::lines(input) {
+            let mut tokenizer = Tokenizer::new(line);
+            for token in &mut tokenizer {
+                match token? {
+                    Token::Number(number) => numbers.push(number),
+                    Token::Symbol(symbol) => symbols.push(symbol),
+                }
+            }
+            width = width.max(tokenizer.x);
+            height = line.0 + 1;
+        }
+
+        let mut number_at = Grid::filled(width, height, None);
+        for (i, number) in numbers.iter().enumerate() {
+            for x in number.x_min..=number.x_max {
+                number_at[Point::new(x, number.y)] = Some(i);
+            }
+        }
+        Ok(Self {
+            numbers,
+            symbols,
+            number_at,
+        })
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    // Indexes of the numbers touching the symbol, in reading order
+    fn adjacent(&self, symbol: &Symbol) -> Vec<usize> {
+        let mut adjacent: Vec<_> = self
+            .number_at
+            .neighbors8(symbol.position)
+            .filter_map(|p| self.number_at[p])
+            .collect();
+        adjacent.sort_unstable();
+        adjacent.dedup();
+        adjacent
+    }
+
+    /// The part numbers touching the symbol
+    pub fn adjacent_parts(&self, symbol: &Symbol) -> Vec<i32> {
+        self.adjacent(symbol)
+            .into_iter()
+            .map(|i| self.numbers[i].value)
+            .collect()
+    }
+
+    /// Every symbol with the part numbers touching it
+    pub fn parts_by_symbol(&self) -> impl Iterator<Item = (&Symbol, Vec<i32>)> {
+        self.symbols.iter().map(|s| (s, self.adjacent_parts(s)))
+    }
+
+    /// The numbers touching at least one symbol
+    pub fn part_numbers(&self) -> Vec<i32> {
+        let mut is_part = vec![false; self.numbers.len()];
+        for symbol in &self.symbols {
+            for i in self.adjacent(symbol) {
+                is_part[i] = true;
+            }
+        }
+        self.numbers
+            .iter()
+            .zip(is_part)
+            .filter(|(_, is_part)| *is_part)
+            .map(|(n, _)| n.value)
+            .collect()
+    }
+
+    /// Sum, over the symbols of the set touched by the right number of parts, of the product
+    /// of those parts. Gear ratios are the `*` touched by exactly two parts
+    /// With many large parts around a symbol, the result can be too large for an i64
+    pub fn sum_of_products(
+        &self,
+        symbols: &[char],
+        adjacency: Adjacency,
+    ) -> Result<i64, PuzzleError> {
+        self.parts_by_symbol()
+            .filter(|(s, parts)| symbols.contains(&s.value) && adjacency.matches(parts.len()))
+            .try_fold(0i64, |sum, (_, parts)| {
+                parts
+                    .iter()
+                    .try_fold(1i64, |product, p| product.checked_mul(i64::from(*p)))
+                    .and_then(|product| sum.checked_add(product))
+            })
+            .ok_or_else(|| PuzzleError::new(DAY, "the sum of products is too large"))
+    }
+
+    /// Statistics of every kind of symbol
+    pub fn statistics(&self) -> BTreeMap<char, SymbolStatistics> {
+        let mut statistics = BTreeMap::<_, SymbolStatistics>::new();
+        for (symbol, parts) in self.parts_by_symbol() {
+            let entry = statistics.entry(symbol.value).or_default();
+            entry.occurrences += 1;
+            entry.parts += parts.len();
+            entry.max_parts = entry.max_parts.max(parts.len());
+        }
+        statistics
+    }
+}
+
+fn run1(input: &str) -> Result<i32, PuzzleError> {
+    Ok(Schematic::from(input)?.part_numbers().iter().sum())
+}
+
+fn run2(input: &str) -> Result<i64, PuzzleError> {
+    Schematic::from(input)?.sum_of_products(&['*'], Adjacency::Exactly(2))
+}
+
+pub struct Day3;
+
+impl crate::Solution for Day3 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run1(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run2(input).map(Into::into)
+    }
+}
+
+#[test]
+fn test_star1() {
+    let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    assert_eq!(Ok(4361), run1(input))
+}
+
+#[test]
+fn test_star2() {
+    let input = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+    assert_eq!(Ok(467835), run2(input))
+}
+
+#[test]
+fn test_number_too_large() {
+    let error = run1("...*........\n.99999999999\n").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 2)));
+}
+
+#[test]
+fn test_parts_by_symbol() {
+    let schematic = Schematic::from(
+        "467..114..
+...*......
+..35..633.
+......#...
+617*......",
+    )
+    .unwrap();
+    let parts: Vec<_> = schematic
+        .parts_by_symbol()
+        .map(|(s, parts)| (s.value, parts))
+        .collect();
+    assert_eq!(
+        vec![('*', vec![467, 35]), ('#', vec![633]), ('*', vec![617])],
+        parts
+    );
+    assert_eq!(vec![467, 35, 633, 617], schematic.part_numbers());
+
+    // A number touching a symbol with several of its digits is only counted once
+    let schematic = Schematic::from("12.\n.*.").unwrap();
+    assert_eq!(vec![12], schematic.adjacent_parts(&schematic.symbols()[0]));
+}
+
+#[test]
+fn test_queries() {
+    let schematic = Schematic::from(
+        "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..",
+    )
+    .unwrap();
+    assert_eq!(
+        Ok(467835),
+        schematic.sum_of_products(&['*'], Adjacency::Exactly(2))
+    );
+    assert_eq!(
+        Ok(467835 + 617),
+        schematic.sum_of_products(&['*'], Adjacency::AtLeast(1))
+    );
+    assert_eq!(
+        Ok(633 + 592 + 664),
+        schematic.sum_of_products(&['#', '+', '$'], Adjacency::Exactly(1))
+    );
+    assert_eq!(
+        Ok(0),
+        schematic.sum_of_products(&['*'], Adjacency::AtLeast(3))
+    );
+
+    let statistics = schematic.statistics();
+    assert_eq!(
+        Some(&SymbolStatistics {
+            occurrences: 3,
+            parts: 5,
+            max_parts: 2
+        }),
+        statistics.get(&'*')
+    );
+    assert_eq!(4, statistics.len());
+}
+
+#[test]
+fn test_tokenizer() {
+    let tokens: Vec<_> = Tokenizer::new((1, "12.€5..#")).collect();
+    assert_eq!(
+        vec![
+            Ok(Token::Number(Number {
+                x_min: 0,
+                x_max: 1,
+                y: 1,
+                value: 12
+            })),
+            Ok(Token::Symbol(Symbol {
+                position: Point::new(3, 1),
+                value: '€'
+            })),
+            Ok(Token::Number(Number {
+                x_min: 4,
+                x_max: 4,
+                y: 1,
+                value: 5
+            })),
+            Ok(Token::Symbol(Symbol {
+                position: Point::new(7, 1),
+                value: '#'
+            })),
+        ],
+        tokens
+    );
+    // The multi-byte symbol counts as a single cell
+    assert_eq!(
+        Ok(vec![5]),
+        Schematic::from("....\n€5..").map(|s| s.part_numbers())
+    );
+}
+
+#[test]
+fn test_unexpected_character() {
+    let error = run1("467..\n..é.a\n").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 3)));
+    assert!(run1("4 7\n").is_err());
+}
+
+#[test]
+fn test_product_too_large() {
+    let schematic = Schematic::from("9999999.9999999\n.......*.......\n......9999999..").unwrap();
+    assert!(schematic
+        .sum_of_products(&['*'], Adjacency::AtLeast(3))
+        .is_err());
+    assert_eq!(
+        Ok(0),
+        schematic.sum_of_products(&['*'], Adjacency::Exactly(2))
+    );
+}
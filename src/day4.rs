@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use crate::PuzzleError;
+
+const DAY: u32 = 4;
+
+fn to_hash_set(line: (usize, &str), numbers: &str) -> Result<HashSet<i32>, PuzzleError> {
+    numbers
+        .split(' ')
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|n| {
+            n.parse()
+                .map_err(|_| PuzzleError::at(DAY, line, n, "invalid number"))
+        })
+        .collect()
+}
+
+fn parse_line((y, line): (usize, &str)) -> Result<usize, PuzzleError> {
+    let (_, b) = line
+        .split_once(": ")
+        .ok_or_else(|| PuzzleError::at(DAY, (y, line), line, "missing ': ' after the card"))?;
+    let (winning, i_have) = b
+        .split_once(" | ")
+        .ok_or_else(|| PuzzleError::at(DAY, (y, line), b, "missing ' | ' between the numbers"))?;
+    Ok(to_hash_set((y, line), winning)?
+        .intersection(&to_hash_set((y, line), i_have)?)
+        .count())
+}
+
+fn run1(input: &str) -> Result<usize, PuzzleError> {
+    crate::lines(input)
+        .map(parse_line)
+        .map(|len| {
+            len.map(|len| {
+                if len < 2 {
+                    len
+                } else {
+                    2_usize.pow(len as u32 - 1)
+                }
+            })
+        })
+        .sum()
+}
+
+fn run2(input: &str) -> Result<i32, PuzzleError> {
+    let matches = crate::lines(input)
+        .map(parse_line)
+        .collect::<Result<Vec<_>, _>>()?;
+    let num_lines = matches.len();
+    let mut counts = vec![1; num_lines];
+    matches
+        .into_iter()
+        .enumerate()
+        .for_each(|(position, count)| {
+            for i in (position + 1)..(position + count + 1).min(num_lines) {
+                counts[i] += counts[position];
+            }
+        });
+    Ok(counts.iter().sum())
+}
+
+pub struct Day4;
+
+impl crate::Solution for Day4 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run1(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run2(input).map(Into::into)
+    }
+}
+
+#[test]
+fn test_star1() {
+    let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+    assert_eq!(Ok(13), run1(input));
+}
+
+#[test]
+fn test_start2() {
+    let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+    assert_eq!(Ok(30), run2(input))
+}
+
+#[test]
+fn test_invalid_number() {
+    let error = run1("Card 1: 41 4x | 83 86\n").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((1, 12)));
+}
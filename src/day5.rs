@@ -0,0 +1,595 @@
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_while1};
+use nom::character::complete::{line_ending, one_of};
+use nom::combinator::{eof, map, map_res, opt, recognize};
+use nom::multi::{many0, many1};
+use nom::sequence::{pair, preceded, separated_pair, terminated, tuple};
+use nom::IResult;
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+
+use crate::PuzzleError;
+
+const DAY: u32 = 5;
+
+fn int(input: &str) -> IResult<&str, usize> {
+    map_res(
+        preceded(opt(tag(" ")), recognize(many1(one_of("0123456789")))),
+        |s: &str| s.parse(),
+    )(input)
+}
+
+fn int_list(input: &str) -> IResult<&str, Vec<usize>> {
+    many1(int)(input)
+}
+
+fn seeds(input: &str) -> IResult<&str, Vec<usize>> {
+    preceded(tag("seeds:"), terminated(int_list, many1(line_ending)))(input)
+}
+
+#[derive(Debug, PartialEq)]
+struct RangedMapping {
+    source: usize,
+    destination: usize,
+    length: usize,
+}
+
+impl RangedMapping {
+    fn location(&self, pos: usize) -> Option<usize> {
+        (pos >= self.source && pos < self.source + self.length)
+            .then(|| self.destination + (pos - self.source))
+    }
+}
+
+/// A segment of positions `[start, end)`, moved as a block to `destination`
+#[derive(Clone, Debug, PartialEq)]
+struct Segment {
+    start: usize,
+    end: usize,
+    destination: usize,
+}
+
+impl Segment {
+    fn apply(&self, pos: usize) -> usize {
+        self.destination + (pos - self.start)
+    }
+
+    fn image(&self) -> Range<usize> {
+        self.destination..self.apply(self.end)
+    }
+}
+
+/// A layer of the almanac as a function over all the positions: sorted segments,
+/// without overlap nor gap, the positions not covered by a mapping being their own image
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self::normalised(vec![Segment {
+            start: 0,
+            end: usize::MAX,
+            destination: 0,
+        }])
+    }
+
+    // When mappings overlap, the first one wins, as in the puzzle statement
+    fn from(mappings: &[RangedMapping]) -> Self {
+        let mut bounds: Vec<usize> = mappings
+            .iter()
+            .flat_map(|m| [m.source, m.source + m.length])
+            .chain([0, usize::MAX])
+            .collect();
+        bounds.sort();
+        bounds.dedup();
+        let segments = bounds
+            .windows(2)
+            .map(|w| Segment {
+                start: w[0],
+                end: w[1],
+                destination: mappings
+                    .iter()
+                    .find_map(|m| m.location(w[0]))
+                    .unwrap_or(w[0]),
+            })
+            .collect();
+        Self::normalised(segments)
+    }
+
+    // Merges the consecutive segments that are moved together
+    fn normalised(segments: Vec<Segment>) -> Self {
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments.into_iter().filter(|s| s.start < s.end) {
+            match merged.last_mut() {
+                Some(last)
+                    if last.end == segment.start && last.apply(last.end) == segment.destination =>
+                {
+                    last.end = segment.end;
+                }
+                _ => merged.push(segment),
+            }
+        }
+        Self { segments: merged }
+    }
+
+    fn segment(&self, pos: usize) -> &Segment {
+        let i = self.segments.partition_point(|s| s.end <= pos);
+        &self.segments[i.min(self.segments.len() - 1)]
+    }
+
+    pub fn apply(&self, pos: usize) -> usize {
+        self.segment(pos).apply(pos)
+    }
+
+    /// Applies `self` then `other`: the image of each segment is split at the bounds of `other`
+    pub fn compose(&self, other: &PiecewiseMap) -> PiecewiseMap {
+        let mut segments = vec![];
+        for segment in &self.segments {
+            let image = segment.image();
+            let first = other.segments.partition_point(|s| s.end <= image.start);
+            for next in other.segments[first..]
+                .iter()
+                .take_while(|s| s.start < image.end)
+            {
+                let from = image.start.max(next.start);
+                let to = image.end.min(next.end);
+                segments.push(Segment {
+                    start: segment.start + (from - image.start),
+                    end: segment.start + (to - image.start),
+                    destination: next.apply(from),
+                });
+            }
+        }
+        Self::normalised(segments)
+    }
+
+    /// The smallest image of the positions in `range`
+    pub fn min_over(&self, range: Range<usize>) -> Option<usize> {
+        let first = self.segments.partition_point(|s| s.end <= range.start);
+        self.segments[first..]
+            .iter()
+            .take_while(|s| s.start < range.end && !range.is_empty())
+            .map(|s| s.apply(s.start.max(range.start)))
+            .min()
+    }
+
+    /// All the positions that are sent to `pos`
+    pub fn preimages(&self, pos: usize) -> Vec<usize> {
+        self.segments
+            .iter()
+            .filter(|s| s.image().contains(&pos))
+            .map(|s| s.start + (pos - s.destination))
+            .collect()
+    }
+
+    /// The reverse map, if every position is the image of exactly one position
+    pub fn inverse(&self) -> Option<PiecewiseMap> {
+        let mut segments: Vec<_> = self
+            .segments
+            .iter()
+            .map(|s| Segment {
+                start: s.destination,
+                end: s.apply(s.end),
+                destination: s.start,
+            })
+            .collect();
+        segments.sort_by_key(|s| s.start);
+        let tiled = segments.first().map(|s| s.start) == Some(0)
+            && segments.last().map(|s| s.end) == Some(usize::MAX)
+            && segments.windows(2).all(|w| w[0].end == w[1].start);
+        tiled.then(|| Self::normalised(segments))
+    }
+}
+
+fn range(input: &str) -> IResult<&str, RangedMapping> {
+    map(tuple((int, int, int)), |(destination, source, length)| {
+        RangedMapping {
+            source,
+            destination,
+            length,
+        }
+    })(input)
+}
+
+fn category(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| c.is_alphanumeric() || c == '_')(input)
+}
+
+// A `source-to-target map:` header followed by its ranges
+type Mapping<'a> = ((&'a str, &'a str), Vec<RangedMapping>);
+
+fn mapping(input: &str) -> IResult<&str, Mapping<'_>> {
+    let header = terminated(
+        separated_pair(category, tag("-to-"), category),
+        pair(tag(" map:"), line_ending),
+    );
+    pair(
+        header,
+        terminated(
+            many1(terminated(range, alt((line_ending, eof)))),
+            many0(line_ending),
+        ),
+    )(input)
+}
+
+/// A map of the almanac, from one category to another
+#[derive(Debug)]
+pub struct Layer {
+    pub source: String,
+    pub target: String,
+    pub map: PiecewiseMap,
+}
+
+pub struct Data {
+    seeds: Vec<usize>,
+    // In the order of the input, that does not need to follow the chain of categories
+    layers: Vec<Layer>,
+}
+
+impl Data {
+    pub fn from(input: &str) -> Result<Self, PuzzleError> {
+        let (rest, (seeds, mappings)) =
+            pair(seeds, many1(mapping))(input).map_err(|err| match err {
+                nom::Err::Error(e) | nom::Err::Failure(e) => {
+                    PuzzleError::in_input(DAY, input, e.input, "could not parse the almanac")
+                }
+                nom::Err::Incomplete(_) => PuzzleError::new(DAY, "the almanac is incomplete"),
+            })?;
+        if !rest.trim().is_empty() {
+            return Err(PuzzleError::in_input(
+                DAY,
+                input,
+                rest,
+                "unexpected input after the last map",
+            ));
+        }
+
+        let mut layers: Vec<Layer> = vec![];
+        for ((source, target), ranges) in mappings {
+            if layers
+                .iter()
+                .any(|l| l.source == source && l.target == target)
+            {
+                return Err(PuzzleError::in_input(
+                    DAY,
+                    input,
+                    source,
+                    format!("the map from {source} to {target} is defined twice"),
+                ));
+            }
+            layers.push(Layer {
+                source: source.to_owned(),
+                target: target.to_owned(),
+                map: PiecewiseMap::from(&ranges),
+            });
+        }
+        Ok(Data { seeds, layers })
+    }
+
+    /// Every category that appears in a map header
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories: Vec<_> = self
+            .layers
+            .iter()
+            .flat_map(|l| [l.source.as_str(), l.target.as_str()])
+            .collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+
+    /// The shortest chain of layers that leads from the `source` category to the `target` one
+    pub fn path(&self, source: &str, target: &str) -> Result<Vec<&Layer>, PuzzleError> {
+        // For each category reached, the layer used to get there
+        let mut reached_by: HashMap<&str, Option<&Layer>> = HashMap::from([(source, None)]);
+        let mut queue = VecDeque::from([source]);
+        while let Some(category) = queue.pop_front() {
+            if category == target {
+                break;
+            }
+            for layer in self.layers.iter().filter(|l| l.source == category) {
+                if !reached_by.contains_key(layer.target.as_str()) {
+                    reached_by.insert(&layer.target, Some(layer));
+                    queue.push_back(&layer.target);
+                }
+            }
+        }
+
+        let mut path = vec![];
+        let mut category = target;
+        while category != source {
+            let layer = reached_by.get(category).copied().flatten().ok_or_else(|| {
+                PuzzleError::new(DAY, format!("no chain of maps from {source} to {target}"))
+            })?;
+            path.push(layer);
+            category = &layer.source;
+        }
+        path.reverse();
+        Ok(path)
+    }
+
+    /// All the layers from `source` to `target` collapsed in a single map
+    pub fn map_between(&self, source: &str, target: &str) -> Result<PiecewiseMap, PuzzleError> {
+        Ok(self
+            .path(source, target)?
+            .iter()
+            .fold(PiecewiseMap::identity(), |map, layer| {
+                map.compose(&layer.map)
+            }))
+    }
+
+    // For the second star, seeds are pairs of (start, length)
+    fn seed_ranges(&self) -> Result<Vec<Range<usize>>, PuzzleError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(PuzzleError::new(
+                DAY,
+                "the seeds must be pairs of start and length",
+            ));
+        }
+        Ok(self
+            .seeds
+            .chunks(2)
+            .map(|pair| pair[0]..pair[0] + pair[1])
+            .collect())
+    }
+}
+
+fn run1(input: &str) -> Result<usize, PuzzleError> {
+    let data = Data::from(input)?;
+    let seed_to_location = data.map_between("seed", "location")?;
+    let destinations = data.seeds.iter().map(|seed| seed_to_location.apply(*seed));
+    destinations
+        .min()
+        .ok_or_else(|| PuzzleError::new(DAY, "no seed in the almanac"))
+}
+
+fn run2(input: &str) -> Result<usize, PuzzleError> {
+    let data = Data::from(input)?;
+    let seed_to_location = data.map_between("seed", "location")?;
+    data.seed_ranges()?
+        .into_iter()
+        .filter_map(|range| seed_to_location.min_over(range))
+        .min()
+        .ok_or_else(|| PuzzleError::new(DAY, "no seed in the almanac"))
+}
+
+pub struct Day5;
+
+impl crate::Solution for Day5 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run1(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run2(input).map(Into::into)
+    }
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+#[test]
+fn test_star1() {
+    assert_eq!(Ok(35), run1(EXAMPLE));
+    assert_eq!(Ok(46), run2(EXAMPLE));
+}
+
+#[test]
+fn test_parse_int() {
+    assert_eq!(int(" 23"), Ok(("", 23)));
+}
+
+#[test]
+fn test_parse_int_list() {
+    assert_eq!(int_list("23 12 14"), Ok(("", vec![23, 12, 14])));
+}
+
+#[test]
+fn test_parse_seed_list() {
+    assert_eq!(
+        seeds("seeds: 79 14 55 13\n"),
+        Ok(("", vec![79, 14, 55, 13]))
+    );
+}
+
+#[test]
+fn test_parse_mapping() {
+    let input = "seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:";
+
+    let expected = vec![
+        RangedMapping {
+            source: 98,
+            destination: 50,
+            length: 2,
+        },
+        RangedMapping {
+            source: 50,
+            destination: 52,
+            length: 48,
+        },
+    ];
+    assert_eq!(mapping(input).unwrap().1, (("seed", "soil"), expected));
+}
+
+#[test]
+fn test_range_location() {
+    let r = RangedMapping {
+        source: 10,
+        destination: 15,
+        length: 10,
+    };
+    assert_eq!(r.location(10), Some(15));
+    assert_eq!(r.location(11), Some(16));
+    assert_eq!(r.location(9), None);
+    assert_eq!(r.location(19), Some(24));
+    assert_eq!(r.location(20), None);
+}
+
+#[test]
+fn test_invalid_almanac() {
+    let input = "seeds: 79 14\r\n\r\nseed-to-soil map:\r\n50 98 x\r\n";
+    let error = Data::from(input).err().unwrap();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((4, 7)));
+}
+
+#[cfg(test)]
+fn example_layer() -> PiecewiseMap {
+    PiecewiseMap::from(&[
+        RangedMapping {
+            source: 98,
+            destination: 50,
+            length: 2,
+        },
+        RangedMapping {
+            source: 50,
+            destination: 52,
+            length: 48,
+        },
+    ])
+}
+
+#[test]
+fn test_piecewise_map_normalisation() {
+    let layer = example_layer();
+    assert_eq!(layer.segments.len(), 4);
+    assert!(layer.segments.windows(2).all(|w| w[0].end == w[1].start));
+    assert_eq!(layer.apply(10), 10);
+    assert_eq!(layer.apply(53), 55);
+    assert_eq!(layer.apply(99), 51);
+    assert_eq!(layer.apply(100), 100);
+
+    let merged = PiecewiseMap::from(&[RangedMapping {
+        source: 10,
+        destination: 10,
+        length: 5,
+    }]);
+    assert_eq!(merged, PiecewiseMap::identity());
+}
+
+#[test]
+fn test_piecewise_map_compose() {
+    let layer = example_layer();
+    let twice = layer.compose(&layer);
+    for pos in [0, 49, 50, 51, 97, 98, 99, 100, 1000] {
+        assert_eq!(twice.apply(pos), layer.apply(layer.apply(pos)));
+    }
+    assert_eq!(layer.compose(&PiecewiseMap::identity()), layer);
+    assert_eq!(PiecewiseMap::identity().compose(&layer), layer);
+}
+
+#[test]
+fn test_piecewise_map_inverse() {
+    let layer = example_layer();
+    let inverse = layer.inverse().unwrap();
+    for pos in [0, 49, 50, 51, 97, 98, 99, 100] {
+        assert_eq!(inverse.apply(layer.apply(pos)), pos);
+    }
+    assert_eq!(layer.compose(&inverse), PiecewiseMap::identity());
+
+    // 10..15 are sent over 20..25, that are kept too: nothing is sent to 10..15
+    let collapsing = PiecewiseMap::from(&[RangedMapping {
+        source: 10,
+        destination: 20,
+        length: 5,
+    }]);
+    assert_eq!(collapsing.inverse(), None);
+    assert_eq!(collapsing.preimages(22), vec![12, 22]);
+    assert_eq!(collapsing.preimages(12), vec![]);
+}
+
+#[test]
+fn test_piecewise_map_min_over() {
+    let layer = example_layer();
+    assert_eq!(layer.min_over(45..60), Some(45));
+    assert_eq!(layer.min_over(60..100), Some(50));
+    assert_eq!(layer.min_over(60..60), None);
+}
+
+#[test]
+fn test_seeds_at_location() {
+    let data = Data::from(EXAMPLE).unwrap();
+    let seed_to_location = data.map_between("seed", "location").unwrap();
+    assert_eq!(seed_to_location.preimages(35), vec![13]);
+    assert_eq!(seed_to_location.preimages(46), vec![82]);
+    assert!(seed_to_location.inverse().is_some());
+}
+
+#[test]
+fn test_chain_of_categories() {
+    let data = Data::from(EXAMPLE).unwrap();
+    assert_eq!(data.categories().len(), 8);
+    let path: Vec<_> = data
+        .path("soil", "humidity")
+        .unwrap()
+        .iter()
+        .map(|l| l.target.as_str())
+        .collect();
+    assert_eq!(
+        path,
+        vec!["fertilizer", "water", "light", "temperature", "humidity"]
+    );
+    assert!(data.path("seed", "seed").unwrap().is_empty());
+    assert!(data.path("location", "seed").is_err());
+    assert!(data.path("seed", "planet").is_err());
+}
+
+#[test]
+fn test_reordered_and_extra_maps() {
+    // The blocks are shuffled, a shortcut and an unrelated category are added
+    let blocks: Vec<_> = EXAMPLE.split("\n\n").collect();
+    let mut shuffled = vec![blocks[0]];
+    shuffled.extend(blocks[1..].iter().rev());
+    shuffled.push("water-to-planet map:\n0 0 10");
+    let input = shuffled.join("\n\n");
+    assert_eq!(Ok(35), run1(&input));
+    assert_eq!(Ok(46), run2(&input));
+
+    let shortcut = format!("{input}\n\nseed-to-location map:\n0 0 100\n");
+    assert_eq!(Ok(13), run1(&shortcut));
+}
+
+#[test]
+fn test_duplicate_map() {
+    let input = format!("{EXAMPLE}\nseed-to-soil map:\n0 0 1\n");
+    let error = Data::from(&input).err().unwrap();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((35, 1)));
+}
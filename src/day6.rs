@@ -0,0 +1,139 @@
+use crate::PuzzleError;
+
+const DAY: u32 = 6;
+
+/*
+distance = speed * (total_time - pressing_time)
+speed = pressing_time
+distance = pressing_time * total_time - pressing_time * pressing_time
+
+solve
+-pressing_time * pressing_time + pressing_time * total_time - distance >= 0
+x = pressing_time
+-x² + t·x - d = 0 (ax² + bx + c ; a=-1 b=t, c=-d)
+*/
+
+fn solve(time: f64, record: f64) -> u64 {
+    let delta = time * time - 4.0 * record; // delta = t² - 4·d (b² - ac)
+    if delta < 0.0 {
+        return 0; // the record can not be beaten
+    }
+    // delta positive, a negative, the hyperbole is pointing up, positive between x1 and x2
+    let x1 = (time - delta.sqrt()) / 2.0;
+    let x2 = (time + delta.sqrt()) / 2.0;
+
+    (x2.floor() as u64 + 1).saturating_sub(x1.ceil() as u64)
+}
+
+// Each line is a header followed by numbers: returns them with the line they were read from
+fn values<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    header: &str,
+) -> Result<((usize, &'a str), &'a str), PuzzleError> {
+    let (y, line) = lines
+        .next()
+        .ok_or_else(|| PuzzleError::new(DAY, format!("missing the {header} line")))?;
+    let values = line
+        .strip_prefix(header)
+        .ok_or_else(|| PuzzleError::at(DAY, (y, line), line, format!("expected {header}")))?;
+    Ok(((y, line), values))
+}
+
+fn number(line: (usize, &str), n: &str) -> Result<f64, PuzzleError> {
+    n.parse::<u64>()
+        .map(|n| n as f64)
+        .map_err(|_| PuzzleError::at(DAY, line, n, "invalid number"))
+}
+
+fn concatenated(line: (usize, &str), values: &str) -> Result<f64, PuzzleError> {
+    values
+        .replace(' ', "")
+        .parse::<u64>()
+        .map(|n| n as f64)
+        .map_err(|_| PuzzleError::at(DAY, line, values.trim_start(), "invalid number"))
+}
+
+fn races(input: &str) -> Result<Vec<(f64, f64)>, PuzzleError> {
+    let mut lines = crate::lines(input);
+    let (time_line, times) = values(&mut lines, "Time:")?;
+    let (distance_line, distances) = values(&mut lines, "Distance:")?;
+    let times = times
+        .split_whitespace()
+        .map(|n| number(time_line, n))
+        .collect::<Result<Vec<_>, _>>()?;
+    let distances = distances
+        .split_whitespace()
+        .map(|n| number(distance_line, n))
+        .collect::<Result<Vec<_>, _>>()?;
+    if times.len() != distances.len() {
+        return Err(PuzzleError::at(
+            DAY,
+            distance_line,
+            distance_line.1,
+            "not as many distances as times",
+        ));
+    }
+    Ok(times.into_iter().zip(distances).collect())
+}
+
+// For the second star, the spaces between the numbers must be ignored
+fn race(input: &str) -> Result<(f64, f64), PuzzleError> {
+    let mut lines = crate::lines(input);
+    let (time_line, time) = values(&mut lines, "Time:")?;
+    let (distance_line, distance) = values(&mut lines, "Distance:")?;
+    Ok((
+        concatenated(time_line, time)?,
+        concatenated(distance_line, distance)?,
+    ))
+}
+
+// The record must be beaten, not only matched: nudge it so that an exact root is excluded
+fn run1(input: &str) -> Result<u64, PuzzleError> {
+    Ok(races(input)?
+        .into_iter()
+        .map(|(time, record)| solve(time, record + 0.5))
+        .product())
+}
+
+fn run2(input: &str) -> Result<u64, PuzzleError> {
+    let (time, record) = race(input)?;
+    Ok(solve(time, record + 0.5))
+}
+
+pub struct Day6;
+
+impl crate::Solution for Day6 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run1(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run2(input).map(Into::into)
+    }
+}
+
+#[test]
+fn test_solve() {
+    assert_eq!(4, solve(7.0, 9.0));
+    assert_eq!(8, solve(15.0, 40.0));
+}
+
+#[test]
+fn test_star1() {
+    let input = "Time:      7  15   30
+Distance:  9  40  200";
+    assert_eq!(Ok(288), run1(input));
+}
+
+#[test]
+fn test_star2() {
+    let input = "Time:      7  15   30
+Distance:  9  40  200";
+    assert_eq!(Ok(71503), run2(input));
+}
+
+#[test]
+fn test_unbeatable_record() {
+    assert_eq!(0, solve(4.0, 4.5));
+    assert_eq!(0, solve(4.0, 10.0));
+}
@@ -0,0 +1,240 @@
+use crate::PuzzleError;
+
+const DAY: u32 = 7;
+
+/// The rules of the first star, or the ones of the second where J are jokers
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ruleset {
+    Standard,
+    Jokers,
+}
+
+// Declared in the standard order
+#[derive(PartialOrd, Ord, PartialEq, Eq, Debug, Hash)]
+enum Card {
+    Num(u32),
+    T,
+    J,
+    Q,
+    K,
+    A,
+}
+
+impl Card {
+    fn from(c: char) -> Option<Self> {
+        match c {
+            'A' => Some(Card::A),
+            'K' => Some(Card::K),
+            'Q' => Some(Card::Q),
+            'J' => Some(Card::J),
+            'T' => Some(Card::T),
+            n => n.to_digit(10).map(Card::Num),
+        }
+    }
+
+    // With jokers, J becomes the weakest card
+    fn strength(&self, ruleset: Ruleset) -> u32 {
+        match (self, ruleset) {
+            (Card::J, Ruleset::Jokers) => 1,
+            (Card::Num(n), _) => *n,
+            (Card::T, _) => 10,
+            (Card::J, Ruleset::Standard) => 11,
+            (Card::Q, _) => 12,
+            (Card::K, _) => 13,
+            (Card::A, _) => 14,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, PartialOrd, Ord, Eq)]
+enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    fn from(hand: &Hand, ruleset: Ruleset) -> Self {
+        use HandType::*;
+        let is_joker = |c: &&Card| ruleset == Ruleset::Jokers && *c == &Card::J;
+        let mut hash = std::collections::HashMap::<_, usize>::new();
+        for card in hand.cards.iter().filter(|c| !is_joker(c)) {
+            *hash.entry(card).or_default() += 1;
+        }
+
+        let jokers = hand.cards.iter().filter(is_joker).count();
+
+        let without_jokers =
+            match [5, 4, 3, 2].map(|v| hash.iter().filter(|(_, val)| **val == v).count()) {
+                [1, _, _, _] => FiveOfAKind,
+                [_, 1, _, _] => FourOfAKind,
+                [_, _, 1, 1] => FullHouse,
+                [_, _, 1, _] => ThreeOfAKind,
+                [_, _, _, 2] => TwoPair,
+                [_, _, _, 1] => OnePair,
+                _ => HighCard,
+            };
+        match (jokers, without_jokers) {
+            (5, _)
+            | (4, _)
+            | (3, OnePair)
+            | (2, ThreeOfAKind)
+            | (1, FourOfAKind)
+            | (_, FiveOfAKind) => FiveOfAKind,
+            (3, _) | (2, OnePair) | (1, ThreeOfAKind) | (_, FourOfAKind) => FourOfAKind,
+            (1, TwoPair) => FullHouse,
+            (2, _) | (1, OnePair) | (_, ThreeOfAKind) => ThreeOfAKind,
+            (1, _) => OnePair,
+            (_, no_jocker) => no_jocker,
+        }
+    }
+}
+
+fn run(input: &str, ruleset: Ruleset) -> Result<usize, PuzzleError> {
+    let mut hands = crate::lines(input)
+        .map(Hand::from)
+        .collect::<Result<Vec<_>, _>>()?;
+    hands.sort_by_cached_key(|hand| hand.strength(ruleset));
+    Ok(hands
+        .iter()
+        .enumerate()
+        .map(|(i, hand)| (i + 1) * hand.bid)
+        .sum())
+}
+
+pub struct Day7;
+
+impl crate::Solution for Day7 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run(input, Ruleset::Standard).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run(input, Ruleset::Jokers).map(Into::into)
+    }
+}
+
+#[derive(PartialEq, Debug, Eq)]
+struct Hand {
+    bid: usize,
+    cards: Vec<Card>,
+}
+
+impl Hand {
+    fn from((y, line): (usize, &str)) -> Result<Self, PuzzleError> {
+        let (card, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| PuzzleError::at(DAY, (y, line), line, "missing bid after the cards"))?;
+        if card.chars().count() != 5 {
+            return Err(PuzzleError::at(DAY, (y, line), card, "a hand has 5 cards"));
+        }
+        let cards = card
+            .char_indices()
+            .map(|(x, c)| {
+                Card::from(c)
+                    .ok_or_else(|| PuzzleError::at(DAY, (y, line), &card[x..], "invalid card"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            cards,
+            bid: bid
+                .parse()
+                .map_err(|_| PuzzleError::at(DAY, (y, line), bid, "invalid bid"))?,
+        })
+    }
+
+    // Hands are ranked by type, then card by card
+    fn strength(&self, ruleset: Ruleset) -> (HandType, Vec<u32>) {
+        (
+            HandType::from(self, ruleset),
+            self.cards.iter().map(|c| c.strength(ruleset)).collect(),
+        )
+    }
+}
+
+#[test]
+fn test_ord_of_enums() {
+    assert!(Card::K < Card::A);
+    assert!(Card::T > Card::Num(9));
+    assert!(Card::Num(8) > Card::Num(7));
+    assert_eq!(Card::K, Card::K);
+    assert_eq!(Card::Num(8), Card::Num(8));
+    assert!(Card::J > Card::T);
+    assert!(Card::J.strength(Ruleset::Standard) > Card::T.strength(Ruleset::Standard));
+    assert!(Card::J.strength(Ruleset::Jokers) < Card::Num(2).strength(Ruleset::Jokers));
+}
+
+#[test]
+fn test_parsing() {
+    assert_eq!(Card::from('A'), Some(Card::A));
+    assert_eq!(Card::from('1'), Some(Card::Num(1)));
+    assert_eq!(Card::from('X'), None);
+}
+
+#[cfg(test)]
+fn hand(line: &str) -> Hand {
+    Hand::from((0, line)).unwrap()
+}
+
+#[test]
+fn test_parse_line() {
+    use Card::*;
+    assert_eq!(
+        Hand {
+            cards: vec![Num(3), Num(2), T, Num(3), K],
+            bid: 765
+        },
+        hand("32T3K 765")
+    );
+}
+
+#[test]
+fn test_hand_type() {
+    use HandType::*;
+    use Ruleset::*;
+    assert_eq!(HandType::from(&hand("32T3K 765"), Jokers), OnePair);
+    assert_eq!(HandType::from(&hand("KK677 765"), Jokers), TwoPair);
+    assert_eq!(HandType::from(&hand("T55J5 765"), Jokers), FourOfAKind);
+    assert_eq!(HandType::from(&hand("KTJJT 765"), Jokers), FourOfAKind);
+    assert_eq!(HandType::from(&hand("QQQJA 765"), Jokers), FourOfAKind);
+    assert!(HandType::from(&hand("KK677 1"), Jokers) > HandType::from(&hand("32T3K 765"), Jokers));
+
+    assert_eq!(HandType::from(&hand("T55J5 765"), Standard), ThreeOfAKind);
+    assert_eq!(HandType::from(&hand("KTJJT 765"), Standard), TwoPair);
+    assert_eq!(HandType::from(&hand("JJJJJ 765"), Standard), FiveOfAKind);
+    assert_eq!(HandType::from(&hand("JJJJJ 765"), Jokers), FiveOfAKind);
+}
+
+#[test]
+fn test_first_star() {
+    let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    assert_eq!(Ok(6440), run(input, Ruleset::Standard))
+}
+
+#[test]
+fn test_second_star() {
+    let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    assert_eq!(Ok(5905), run(input, Ruleset::Jokers))
+}
+
+#[test]
+fn test_invalid_hand() {
+    let error = run("32T3K 765\nT5XJ5 684", Ruleset::Standard).unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 3)));
+    let error = run("32T3K 765\nT55J5 6a4", Ruleset::Jokers).unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 7)));
+}
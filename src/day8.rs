@@ -0,0 +1,597 @@
+use std::collections::HashMap;
+
+use crate::PuzzleError;
+
+const DAY: u32 = 8;
+
+// Labels are three digits or capital letters: 36³ codes, that fit in a u16
+const LABEL_CODES: usize = 36 * 36 * 36;
+const NO_NODE: u16 = u16::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Turn {
+    Left,
+    Right,
+}
+
+type Node<'a> = (&'a str, (&'a str, &'a str));
+
+// The labels are slices of the line, so that errors can point at them
+// The layout is fixed, so the line is cut at known offsets rather than matched
+fn parse_node((y, line): (usize, &str)) -> Result<Node<'_>, PuzzleError> {
+    let node = line.trim();
+    let label = |start: usize| {
+        node.get(start..start + 3).filter(|l| {
+            l.bytes()
+                .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        })
+    };
+    match (
+        label(0),
+        node.get(3..7),
+        label(7),
+        node.get(10..12),
+        label(12),
+        node.get(15..),
+    ) {
+        (Some(from), Some(" = ("), Some(left), Some(", "), Some(right), Some(")")) => {
+            Ok((from, (left, right)))
+        }
+        _ => Err(PuzzleError::at(
+            DAY,
+            (y, line),
+            line,
+            "expected `AAA = (BBB, CCC)`",
+        )),
+    }
+}
+
+fn code(label: &str) -> usize {
+    label
+        .bytes()
+        .map(|b| match b {
+            b'0'..=b'9' => (b - b'0') as usize,
+            _ => (b - b'A') as usize + 10,
+        })
+        .fold(0, |code, digit| code * 36 + digit)
+}
+
+/// The network of the desert: nodes are interned in dense ids, in the order of the input,
+/// and their successors are stored in flat arrays
+pub struct Network {
+    instructions: Vec<Turn>,
+    labels: Vec<String>,
+    ids: Vec<u16>, // indexed by the code of the label
+    left: Vec<u16>,
+    right: Vec<u16>,
+}
+
+impl Network {
+    pub fn from(input: &str) -> Result<Self, PuzzleError> {
+        let mut lines = crate::lines(input);
+        let (y, line) = lines
+            .next()
+            .ok_or_else(|| PuzzleError::new(DAY, "missing instructions"))?;
+        let instructions = line
+            .trim()
+            .char_indices()
+            .map(|(x, c)| match c {
+                'L' => Ok(Turn::Left),
+                'R' => Ok(Turn::Right),
+                _ => Err(PuzzleError::at(
+                    DAY,
+                    (y, line),
+                    &line.trim()[x..],
+                    "expected L or R",
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let nodes = lines
+            .map(|line| parse_node(line).map(|node| (line, node)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut network = Network {
+            instructions,
+            labels: vec![],
+            ids: vec![NO_NODE; LABEL_CODES],
+            left: vec![],
+            right: vec![],
+        };
+        for (line, (label, _)) in &nodes {
+            if network.id(label).is_some() {
+                return Err(PuzzleError::at(DAY, *line, label, "node defined twice"));
+            }
+            network.ids[code(label)] = network.labels.len() as u16;
+            network.labels.push(label.to_string());
+        }
+        for (line, (_, (left, right))) in &nodes {
+            let succ = |label| {
+                network
+                    .id(label)
+                    .ok_or_else(|| PuzzleError::at(DAY, *line, label, "unknown node"))
+            };
+            let (left, right) = (succ(left)?, succ(right)?);
+            network.left.push(left);
+            network.right.push(right);
+        }
+        Ok(network)
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn id(&self, label: &str) -> Option<u16> {
+        let valid = label.len() == 3
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase());
+        valid
+            .then(|| self.ids[code(label)])
+            .filter(|id| *id != NO_NODE)
+    }
+
+    pub fn label(&self, node: u16) -> &str {
+        &self.labels[node as usize]
+    }
+
+    /// All the nodes, by increasing id
+    pub fn nodes(&self) -> impl Iterator<Item = u16> {
+        0..self.labels.len() as u16
+    }
+
+    /// The node reached from `node` when executing the instruction at `position`
+    pub fn step(&self, node: u16, position: usize) -> u16 {
+        match self.instructions[position % self.instructions.len()] {
+            Turn::Left => self.left[node as usize],
+            Turn::Right => self.right[node as usize],
+        }
+    }
+
+    /// Fast-forwards walks towards the nodes matching `is_target`: see [`Jumps`]
+    pub fn jumps(&self, is_target: impl Fn(u16) -> bool) -> Jumps<'_> {
+        Jumps::new(self, is_target)
+    }
+
+    /// Number of steps from `start` to the first node matching `predicate`,
+    /// or None if the walk never reaches it
+    pub fn walk(&self, start: u16, predicate: impl Fn(u16) -> bool) -> Option<usize> {
+        self.jumps(predicate).first_hit(start)
+    }
+
+    /// Follows the instructions from `start` until a state (node and position in the instructions)
+    /// is repeated, recording the steps on nodes ending with Z
+    pub fn cycle_of(&self, start: u16) -> Cycle {
+        // Only the states of this walk are stored, not every node at every position
+        let mut seen = HashMap::new();
+        let mut hits = vec![];
+        let mut current = start;
+        let mut steps = 0;
+        loop {
+            let position = steps % self.instructions.len();
+            if let Some(offset) = seen.insert((current, position), steps) {
+                return Cycle {
+                    offset,
+                    period: steps - offset,
+                    hits,
+                };
+            }
+            if self.label(current).ends_with('Z') {
+                hits.push(steps);
+            }
+            current = self.step(current, position);
+            steps += 1;
+        }
+    }
+}
+
+// Any number of passes that fits in a usize can be jumped over
+const JUMP_LEVELS: usize = usize::BITS as usize;
+
+/// Jump tables over whole passes of the instructions (always starting at the first one),
+/// to fast-forward a walk in logarithmic time instead of simulating every step
+pub struct Jumps<'a> {
+    network: &'a Network,
+    // passes[k][node]: where a walk starting at node is after 2^k passes
+    passes: Vec<Vec<u16>>,
+    // hits[k][node]: whether a target is met during those 2^k passes (end excluded)
+    hits: Vec<Vec<bool>>,
+    // First step of a single pass that is on a target
+    first_hit: Vec<Option<usize>>,
+}
+
+impl<'a> Jumps<'a> {
+    fn new(network: &'a Network, is_target: impl Fn(u16) -> bool) -> Self {
+        let instructions = network.instructions.len();
+        let mut one_pass = Vec::with_capacity(network.len());
+        let mut first_hit = Vec::with_capacity(network.len());
+        for node in network.nodes() {
+            let mut current = node;
+            let mut hit = None;
+            for position in 0..instructions {
+                if hit.is_none() && is_target(current) {
+                    hit = Some(position);
+                }
+                current = network.step(current, position);
+            }
+            one_pass.push(current);
+            first_hit.push(hit);
+        }
+
+        let mut passes = vec![one_pass];
+        let mut hits = vec![first_hit.iter().map(Option::is_some).collect::<Vec<_>>()];
+        for k in 1..JUMP_LEVELS {
+            let (previous, previous_hits) = (&passes[k - 1], &hits[k - 1]);
+            let next = previous.iter().map(|n| previous[*n as usize]).collect();
+            let next_hits = network
+                .nodes()
+                .map(|n| previous_hits[n as usize] || previous_hits[previous[n as usize] as usize])
+                .collect();
+            passes.push(next);
+            hits.push(next_hits);
+        }
+        Self {
+            network,
+            passes,
+            hits,
+            first_hit,
+        }
+    }
+
+    /// The node reached after `steps` steps from `start`
+    pub fn position_after(&self, start: u16, steps: usize) -> u16 {
+        let instructions = self.network.instructions.len();
+        let (mut full_passes, remainder) = (steps / instructions, steps % instructions);
+        let mut current = start;
+        let mut k = 0;
+        while full_passes > 0 {
+            if full_passes & 1 == 1 {
+                current = self.passes[k][current as usize];
+            }
+            full_passes >>= 1;
+            k += 1;
+        }
+        (0..remainder).fold(current, |node, position| self.network.step(node, position))
+    }
+
+    /// The number of steps before the first target is met,
+    /// or None if it is never met (or only after more steps than a usize can count)
+    pub fn first_hit(&self, start: u16) -> Option<usize> {
+        let mut current = start;
+        let mut full_passes: usize = 0;
+        for k in (0..JUMP_LEVELS).rev() {
+            if !self.hits[k][current as usize] {
+                current = self.passes[k][current as usize];
+                full_passes += 1 << k;
+            }
+        }
+        let hit = self.first_hit[current as usize]?;
+        full_passes
+            .checked_mul(self.network.instructions.len())?
+            .checked_add(hit)
+    }
+}
+
+fn run1(input: &str) -> Result<usize, PuzzleError> {
+    let network = Network::from(input)?;
+    let start = network
+        .id("AAA")
+        .ok_or_else(|| PuzzleError::new(DAY, "missing the start node AAA"))?;
+    let end = network
+        .id("ZZZ")
+        .ok_or_else(|| PuzzleError::new(DAY, "missing the end node ZZZ"))?;
+    network
+        .walk(start, |node| node == end)
+        .ok_or_else(|| PuzzleError::new(DAY, "ZZZ can not be reached from AAA"))
+}
+
+/// The path of a ghost ends up looping over the same states (node and position in the instructions)
+#[derive(Debug, PartialEq)]
+pub struct Cycle {
+    pub offset: usize, // first step of the loop
+    pub period: usize,
+    pub hits: Vec<usize>, // steps on a Z node, before `offset + period`
+}
+
+impl Cycle {
+    /// Whether the ghost is on a Z node after `step` steps
+    pub fn is_hit(&self, step: usize) -> bool {
+        if step < self.offset {
+            self.hits.contains(&step)
+        } else {
+            let folded = self.offset + (step - self.offset) % self.period;
+            self.hits.contains(&folded)
+        }
+    }
+
+    // Once in the loop, a Z node is reached at every step congruent to one of those
+    fn residues(&self) -> Vec<usize> {
+        self.hits
+            .iter()
+            .filter(|hit| **hit >= self.offset)
+            .map(|hit| hit % self.period)
+            .collect()
+    }
+}
+
+// Returns (g, x, y) such that a·x + b·y = g = gcd(a, b)
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Generalized chinese remainder theorem: the moduli do not need to be coprime
+/// Solves x ≡ a (mod m) and x ≡ b (mod n), as x ≡ result.0 (mod result.1)
+fn crt((a, m): (i128, i128), (b, n): (i128, i128)) -> Option<(i128, i128)> {
+    let (g, p, _) = extended_gcd(m, n);
+    if (b - a) % g != 0 {
+        return None;
+    }
+    let lcm = m / g * n;
+    let k = ((b - a) / g * p).rem_euclid(n / g);
+    Some(((a + m * k).rem_euclid(lcm), lcm))
+}
+
+/// The first step where every ghost is on a Z node
+fn first_common_hit(cycles: &[Cycle]) -> Option<usize> {
+    let transient = cycles.iter().map(|c| c.offset).max()?;
+
+    // Before every ghost is in its loop, every step must be checked
+    if let Some(hit) = (0..transient).find(|step| cycles.iter().all(|c| c.is_hit(*step))) {
+        return Some(hit);
+    }
+
+    // Afterwards, only the residues matter. When every ghost reaches Z at the end of its loop
+    // (all residues zero, as in the puzzle input) this is the least common multiple of the periods
+    let mut solutions: Vec<(i128, i128)> = vec![(0, 1)];
+    for cycle in cycles {
+        let period = cycle.period as i128;
+        solutions = solutions
+            .iter()
+            .flat_map(|solution| {
+                cycle
+                    .residues()
+                    .into_iter()
+                    .filter_map(move |r| crt(*solution, (r as i128, period)))
+            })
+            .collect();
+        solutions.sort();
+        solutions.dedup();
+    }
+    solutions
+        .into_iter()
+        .map(|(r, modulus)| {
+            let transient = transient as i128;
+            if r >= transient {
+                r
+            } else {
+                r + (transient - r + modulus - 1) / modulus * modulus
+            }
+        })
+        .min()
+        .map(|step| step as usize)
+}
+
+fn run2(input: &str) -> Result<usize, PuzzleError> {
+    let network = Network::from(input)?;
+    let is_z = |node| network.label(node).ends_with('Z');
+    let starts: Vec<_> = network
+        .nodes()
+        .filter(|node| network.label(*node).ends_with('A'))
+        .collect();
+    let cycles: Vec<_> = starts.iter().map(|node| network.cycle_of(*node)).collect();
+    if cycles.is_empty() {
+        return Err(PuzzleError::new(DAY, "no start node ending with A"));
+    }
+    let step = first_common_hit(&cycles)
+        .ok_or_else(|| PuzzleError::new(DAY, "the ghosts are never all on Z nodes at once"))?;
+    // The answer can be checked without simulating that many steps
+    let jumps = network.jumps(is_z);
+    debug_assert!(starts.iter().all(|s| is_z(jumps.position_after(*s, step))));
+    Ok(step)
+}
+
+pub struct Day8;
+
+impl crate::Solution for Day8 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run1(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run2(input).map(Into::into)
+    }
+}
+
+#[test]
+fn test_star1() {
+    let input = "RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)";
+
+    assert_eq!(Ok(2), run1(input));
+
+    let input = "LLR
+    
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+    assert_eq!(Ok(6), run1(input));
+}
+
+#[test]
+fn test_star2() {
+    let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+
+    assert_eq!(Ok(6), run2(input))
+}
+
+#[test]
+fn test_unknown_node() {
+    let input = "LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+    let error = run1(input).unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((3, 8)));
+}
+
+#[test]
+fn test_malformed_node() {
+    for node in [
+        "AAA = (BBB,CCC)",
+        "AAA = (BBB, CCC",
+        "aaa = (BBB, CCC)",
+        "AAA = (BBB, CCC))",
+    ] {
+        let error = Network::from(&format!("L\n\n{node}")).err().unwrap();
+        assert_eq!(error.location.map(|l| (l.line, l.column)), Some((3, 1)));
+    }
+    assert!(Network::from("L\n\nAÀA = (BBB, CCC)").is_err());
+}
+
+#[test]
+fn test_crt() {
+    assert_eq!(crt((2, 3), (3, 5)), Some((8, 15)));
+    assert_eq!(crt((0, 4), (0, 6)), Some((0, 12)));
+    assert_eq!(crt((1, 4), (3, 6)), Some((9, 12)));
+    assert_eq!(crt((1, 4), (2, 6)), None);
+}
+
+#[test]
+fn test_cycles() {
+    let cycle = |offset, period, hits: &[usize]| Cycle {
+        offset,
+        period,
+        hits: hits.to_vec(),
+    };
+    // Aligned on the periods: least common multiple
+    assert_eq!(
+        Some(6),
+        first_common_hit(&[cycle(1, 2, &[2]), cycle(1, 3, &[3])])
+    );
+    // Shifted: 7 ≡ 1 (mod 3) and 7 ≡ 2 (mod 5)
+    assert_eq!(
+        Some(7),
+        first_common_hit(&[cycle(0, 3, &[1]), cycle(0, 5, &[2])])
+    );
+    // Common hit before one of the ghosts is in its loop
+    assert_eq!(
+        Some(4),
+        first_common_hit(&[cycle(10, 2, &[4, 11]), cycle(0, 4, &[0])])
+    );
+    // Several Z nodes in a loop
+    assert_eq!(
+        Some(5),
+        first_common_hit(&[cycle(0, 6, &[1, 5]), cycle(0, 5, &[0])])
+    );
+    assert_eq!(
+        None,
+        first_common_hit(&[cycle(0, 2, &[0]), cycle(0, 2, &[1])])
+    );
+}
+
+#[test]
+fn test_network() {
+    let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+XXX = (XXX, XXX)";
+    let network = Network::from(input).unwrap();
+    assert_eq!(network.len(), 4);
+    assert_eq!(network.id("11B"), Some(1));
+    assert_eq!(network.id("ZZZ"), None);
+    assert_eq!(network.id("a"), None);
+    assert_eq!(network.label(2), "11Z");
+    assert_eq!(network.step(0, 0), 1);
+    assert_eq!(network.step(1, 1), 2);
+    assert_eq!(
+        network.walk(0, |n| network.label(n).ends_with('Z')),
+        Some(2)
+    );
+    assert_eq!(network.walk(3, |n| n == 0), None);
+    assert_eq!(
+        network.cycle_of(0),
+        Cycle {
+            offset: 1,
+            period: 2,
+            hits: vec![2]
+        }
+    );
+}
+
+#[test]
+fn test_duplicate_node() {
+    let error = Network::from("L\n\nAAA = (AAA, AAA)\nAAA = (AAA, AAA)")
+        .err()
+        .unwrap();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((4, 1)));
+}
+
+#[test]
+fn test_jumps() {
+    let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+    let network = Network::from(input).unwrap();
+    let is_z = |n| network.label(n).ends_with('Z');
+    let jumps = network.jumps(is_z);
+
+    for start in network.nodes() {
+        let mut current = start;
+        for steps in 0..20 {
+            assert_eq!(jumps.position_after(start, steps), current);
+            current = network.step(current, steps);
+        }
+        let simulated = (0..100).find(|steps| is_z(jumps.position_after(start, *steps)));
+        assert_eq!(jumps.first_hit(start), simulated);
+    }
+
+    // The answer of the second star can be checked without simulating
+    let starts: Vec<_> = network
+        .nodes()
+        .filter(|n| network.label(*n).ends_with('A'))
+        .collect();
+    assert!(starts.iter().all(|s| is_z(jumps.position_after(*s, 6))));
+    assert!(!starts.iter().all(|s| is_z(jumps.position_after(*s, 5))));
+    // 22A reaches 22Z every 3 steps, 11A reaches 11Z every 2 steps
+    let far = 6_000_000_000_000;
+    assert!(starts.iter().all(|s| is_z(jumps.position_after(*s, far))));
+    assert_eq!(jumps.first_hit(network.id("XXX").unwrap()), None);
+    // Beyond 2⁴⁸ passes, up to the largest number of steps
+    assert!(starts
+        .iter()
+        .all(|s| is_z(jumps.position_after(*s, 6 << 60))));
+    // 2⁶⁴ - 2 steps after 22B, as 2⁶⁴ ≡ 1 (mod 3)
+    let far = jumps.position_after(network.id("22A").unwrap(), usize::MAX);
+    assert_eq!("22Z", network.label(far));
+}
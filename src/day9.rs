@@ -0,0 +1,97 @@
+use crate::PuzzleError;
+
+const DAY: u32 = 9;
+
+fn parse_line((y, line): (usize, &str)) -> Result<Vec<i32>, PuzzleError> {
+    line.split_whitespace()
+        .map(|i| {
+            i.parse::<i32>()
+                .map_err(|_| PuzzleError::at(DAY, (y, line), i, "invalid number"))
+        })
+        .collect()
+}
+
+fn handle_line(line: (usize, &str)) -> Result<i32, PuzzleError> {
+    let mut data = parse_line(line)?;
+    let mut result = 0;
+    while let Some(last) = data.last().filter(|_| data.iter().any(|i| *i != 0)) {
+        result += last;
+        data = data.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+    Ok(result)
+}
+
+fn handle_line2(line: (usize, &str)) -> Result<i32, PuzzleError> {
+    let mut data = parse_line(line)?;
+    let mut result = data.first().copied().unwrap_or_default();
+    let mut count = 0;
+    while data.iter().any(|i| *i != 0) {
+        data = data.windows(2).map(|w| w[1] - w[0]).collect();
+        result = data.first().ok_or_else(|| {
+            PuzzleError::at(DAY, line, line.1, "the differences never reach zero")
+        })? - result;
+        count += 1;
+    }
+    if count % 2 == 0 {
+        Ok(result)
+    } else {
+        Ok(-result)
+    }
+}
+
+fn run1(input: &str) -> Result<i32, PuzzleError> {
+    crate::lines(input).map(handle_line).sum()
+}
+
+fn run2(input: &str) -> Result<i32, PuzzleError> {
+    crate::lines(input).map(handle_line2).sum()
+}
+
+pub struct Day9;
+
+impl crate::Solution for Day9 {
+    fn part1(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run1(input).map(Into::into)
+    }
+
+    fn part2(&self, input: &str) -> Result<crate::Answer, PuzzleError> {
+        run2(input).map(Into::into)
+    }
+}
+
+#[test]
+fn test_line() {
+    assert_eq!(Ok(18), handle_line((0, "0 3 6 9 12 15")));
+}
+
+#[test]
+fn test_star1() {
+    let input = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    assert_eq!(Ok(114), run1(input))
+}
+
+#[test]
+fn test_line2() {
+    assert_eq!(Ok(-3), handle_line2((0, "0 3 6 9 12 15")));
+    assert_eq!(Ok(0), handle_line2((0, "1 3 6 10 15 21")));
+    assert_eq!(Ok(5), handle_line2((0, "10 13 16 21 30 45")));
+}
+
+#[test]
+fn test_star2() {
+    let input = "0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45";
+
+    assert_eq!(Ok(2), run2(input))
+}
+
+#[test]
+fn test_invalid_line() {
+    let error = run1("0 3 6\n1 x 6\n").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 3)));
+    assert!(handle_line2((0, "5")).is_err());
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+
+// Lines longer than this are shown as a window around the faulty column
+const SNIPPET_WIDTH: usize = 60;
+
+/// Where, in the input, a puzzle could not be understood
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,   // starting at 1, as in any text editor
+    pub column: usize, // starting at 1, counted in characters and not bytes
+    pub snippet: String,
+}
+
+/// Raised when the input of a day is not what the puzzle describes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PuzzleError {
+    pub day: u32,
+    pub location: Option<Location>,
+    pub message: String,
+}
+
+impl PuzzleError {
+    /// An error about the input as a whole (a missing start node, no seed…)
+    pub fn new(day: u32, message: impl Into<String>) -> Self {
+        Self {
+            day,
+            location: None,
+            message: message.into(),
+        }
+    }
+
+    /// An error pointing at `fragment`, that must be a slice of `line`
+    /// `y` is the index of the line, as returned by `enumerate`
+    pub fn at(
+        day: u32,
+        (y, line): (usize, &str),
+        fragment: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        let offset = offset(line, fragment);
+        let column = line[..offset].chars().count() + 1;
+        let skipped = column.saturating_sub(SNIPPET_WIDTH / 2 + 1);
+        Self {
+            day,
+            location: Some(Location {
+                line: y + 1,
+                column,
+                snippet: line.chars().skip(skipped).take(SNIPPET_WIDTH).collect(),
+            }),
+            message: message.into(),
+        }
+    }
+
+    /// An error pointing at `fragment`, that must be a slice of the whole `input`
+    /// Useful for parsers (like nom’s) that do not work line by line
+    pub fn in_input(day: u32, input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = offset(input, fragment);
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let y = input[..start].matches('\n').count();
+        let line = input[start..end].trim_end_matches('\r');
+        let fragment = &input[offset.min(start + line.len())..end];
+        Self::at(day, (y, line), fragment, message)
+    }
+}
+
+// Position of `fragment` in `text`, clamped to the end of `text` if it is not a slice of it
+fn offset(text: &str, fragment: &str) -> usize {
+    (fragment.as_ptr() as usize)
+        .checked_sub(text.as_ptr() as usize)
+        .map_or(text.len(), |offset| offset.min(text.len()))
+}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            None => write!(f, "day {}: {}", self.day, self.message),
+            Some(location) => {
+                writeln!(
+                    f,
+                    "day {}, line {}, column {}: {}",
+                    self.day, location.line, location.column, self.message
+                )?;
+                let skipped = location.column.saturating_sub(SNIPPET_WIDTH / 2 + 1);
+                writeln!(f, "    {}", location.snippet)?;
+                write!(f, "    {:>1$}", "^", location.column - skipped)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+#[test]
+fn test_error_at_fragment() {
+    let line = "32T3K x65";
+    let error = PuzzleError::at(7, (2, line), &line[6..], "invalid bid");
+    assert_eq!(
+        error.location,
+        Some(Location {
+            line: 3,
+            column: 7,
+            snippet: line.to_owned()
+        })
+    );
+    assert_eq!(
+        error.to_string(),
+        "day 7, line 3, column 7: invalid bid\n    32T3K x65\n          ^"
+    );
+}
+
+#[test]
+fn test_error_in_input() {
+    let input = "seeds: 79 14\r\n\r\nseed-to-soil map:\r\n50 98 x\r\n";
+    let error = PuzzleError::in_input(5, input, &input[41..], "expected a number");
+    let location = error.location.unwrap();
+    assert_eq!(location.line, 4);
+    assert_eq!(location.column, 7);
+    assert_eq!(location.snippet, "50 98 x");
+}
+
+#[test]
+fn test_error_columns_are_characters() {
+    let line = "é€x";
+    let error = PuzzleError::at(1, (0, line), &line[5..], "unexpected");
+    assert_eq!(error.location.unwrap().column, 3);
+}
+
+#[test]
+fn test_error_long_line_snippet() {
+    let line = "a".repeat(100) + "!";
+    let error = PuzzleError::at(3, (0, &line), &line[100..], "unexpected symbol");
+    assert!(error
+        .to_string()
+        .ends_with(&format!("{}!\n    {}^", "a".repeat(30), " ".repeat(30))));
+}
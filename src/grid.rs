@@ -0,0 +1,304 @@
+use std::ops::{Index, IndexMut};
+
+use crate::PuzzleError;
+
+/// A cell of a grid: `x` is the column and `y` the row, both starting at 0 from the top left
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    // None when it would leave the grid by the top or the left
+    fn offset(self, dx: isize, dy: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    /// How `x` and `y` change when moving one cell in that direction
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// A rectangular grid of cells, stored row after row
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid by calling `cell` on every character of the input
+    /// Characters for which it returns None are reported as errors with `message`
+    pub fn parse(
+        day: u32,
+        input: &str,
+        cell: impl Fn(char) -> Option<T>,
+        message: &str,
+    ) -> Result<Self, PuzzleError> {
+        // A trailing newline is not a row of the grid
+        let input = input.trim_end();
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            if line.chars().count() != width {
+                return Err(PuzzleError::at(
+                    day,
+                    (y, line),
+                    line,
+                    "all the rows must have the same width",
+                ));
+            }
+            for (offset, c) in line.char_indices() {
+                cells
+                    .push(cell(c).ok_or_else(|| {
+                        PuzzleError::at(day, (y, line), &line[offset..], message)
+                    })?);
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// A grid where the value of every cell is computed from its position
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point { x, y }))
+            .map(&mut cell)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.y * self.width + p.x])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.y * self.width + p.x])
+        } else {
+            None
+        }
+    }
+
+    /// The neighbouring cell in that direction, if it is still in the grid
+    pub fn step(&self, p: Point, direction: Direction) -> Option<Point> {
+        let (dx, dy) = direction.delta();
+        p.offset(dx, dy).filter(|p| self.contains(*p))
+    }
+
+    /// The cells sharing a side with `p`
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(p, direction))
+    }
+
+    /// The cells sharing a side or a corner with `p`
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        (-1..=1)
+            .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+            .filter(|delta| *delta != (0, 0))
+            .filter_map(move |(dx, dy)| p.offset(dx, dy))
+            .filter(|n| self.contains(*n))
+    }
+
+    /// Every position of the grid, row after row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point { x, y }))
+    }
+
+    /// Every cell with its position, row after row
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on empty chunks, and an empty grid has no row anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        // Like row, rather than wrapping around to the next row
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Rows become columns
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// A quarter turn clockwise
+    pub fn rotate(&self) -> Self {
+        Self::from_fn(self.height, self.width, |p| {
+            self[Point::new(p.y, self.height - 1 - p.x)].clone()
+        })
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        assert!(self.contains(p), "{p:?} is outside of the grid");
+        &self.cells[p.y * self.width + p.x]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        assert!(self.contains(p), "{p:?} is outside of the grid");
+        &mut self.cells[p.y * self.width + p.x]
+    }
+}
+
+/// An error about the cell at `p` of a grid parsed from `input`
+pub fn error_at(day: u32, input: &str, p: Point, message: &str) -> PuzzleError {
+    let line = input.lines().nth(p.y).unwrap_or_default();
+    let offset = line.char_indices().nth(p.x).map_or(line.len(), |(i, _)| i);
+    PuzzleError::at(day, (p.y, line), &line[offset..], message)
+}
+
+#[cfg(test)]
+fn chars(input: &str) -> Grid<char> {
+    Grid::parse(0, input, Some, "").unwrap()
+}
+
+#[test]
+fn test_parse() {
+    let grid = chars("ab.\n.cd\n");
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(Some(&'c'), grid.get(Point::new(1, 1)));
+    assert_eq!(None, grid.get(Point::new(3, 0)));
+    assert_eq!('d', grid[Point::new(2, 1)]);
+
+    let error = Grid::parse(4, "12\n3x\n", |c| c.to_digit(10), "not a digit").unwrap_err();
+    assert_eq!(error.location.map(|l| (l.line, l.column)), Some((2, 2)));
+    assert!(Grid::parse(4, "12\n3\n", |c| c.to_digit(10), "").is_err());
+}
+
+#[test]
+fn test_neighbors() {
+    let grid = chars("abc\ndef\nghi");
+    let corner: Vec<_> = grid.neighbors4(Point::new(0, 0)).collect();
+    assert_eq!(vec![Point::new(0, 1), Point::new(1, 0)], corner);
+    assert_eq!(3, grid.neighbors8(Point::new(2, 2)).count());
+    assert_eq!(8, grid.neighbors8(Point::new(1, 1)).count());
+    assert_eq!(None, grid.step(Point::new(2, 1), Direction::Right));
+    assert_eq!(
+        Some(Point::new(1, 0)),
+        grid.step(Point::new(1, 1), Direction::Up)
+    );
+}
+
+#[test]
+fn test_rows_and_columns() {
+    let grid = chars("abc\ndef");
+    assert_eq!(
+        vec![&['a', 'b', 'c'][..], &['d', 'e', 'f']],
+        grid.rows().collect::<Vec<_>>()
+    );
+    assert_eq!(vec!['b', 'e'], grid.column(1).copied().collect::<Vec<_>>());
+    assert_eq!(3, grid.columns().count());
+    assert_eq!(chars("ad\nbe\ncf"), grid.transpose());
+    assert_eq!(chars("da\neb\nfc"), grid.rotate());
+    assert_eq!(grid, grid.rotate().rotate().rotate().rotate());
+}
+
+#[test]
+#[should_panic(expected = "column 4 is outside of the grid")]
+fn test_column_outside() {
+    chars("abc\ndef").column(4).count();
+}
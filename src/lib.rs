@@ -0,0 +1,135 @@
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod grid;
+
+use std::fmt;
+
+pub use error::PuzzleError;
+
+/// The value of a star, whatever the type the puzzle computes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Str(String),
+    // The part has not been solved (yet): nothing meaningful to print
+    NotImplemented,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::BigInt(n) => write!(f, "{n}"),
+            Answer::Str(s) => write!(f, "{s}"),
+            Answer::NotImplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Int(n.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Int(n.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        i64::try_from(n).map_or(Answer::BigInt(n.into()), Answer::Int)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::from(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_owned())
+    }
+}
+
+/// A puzzle of the calendar: both stars are computed from the raw input
+pub trait Solution {
+    fn part1(&self, input: &str) -> Result<Answer, PuzzleError>;
+    fn part2(&self, input: &str) -> Result<Answer, PuzzleError>;
+}
+
+/// The lines of the input with their index, ignoring blank ones (like a trailing newline)
+/// `str::lines` already strips the carriage return of CRLF line endings
+pub(crate) fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+}
+
+const REGISTRY: &[(u32, &dyn Solution)] = &[
+    (1, &day1::Day1),
+    (2, &day2::Day2),
+    (3, &day3::Day3),
+    (4, &day4::Day4),
+    (5, &day5::Day5),
+    (6, &day6::Day6),
+    (7, &day7::Day7),
+    (8, &day8::Day8),
+    (9, &day9::Day9),
+    (10, &day10::Day10),
+];
+
+/// All the days that have a solution, in calendar order
+pub fn days() -> impl Iterator<Item = (u32, &'static dyn Solution)> {
+    REGISTRY.iter().copied()
+}
+
+/// Returns the solution of the given day, if it has been written
+pub fn solution(day: u32) -> Option<&'static dyn Solution> {
+    days()
+        .find(|(d, _)| *d == day)
+        .map(|(_, solution)| solution)
+}
+
+#[test]
+fn test_answer_display() {
+    assert_eq!("42", Answer::from(42).to_string());
+    assert_eq!("-3", Answer::from(-3_i64).to_string());
+    assert_eq!(Answer::BigInt(u64::MAX.into()), Answer::from(u64::MAX));
+    assert_eq!("meh", Answer::from("meh").to_string());
+    assert_eq!("not implemented", Answer::NotImplemented.to_string());
+}
+
+#[test]
+fn test_registry() {
+    assert!(days().map(|(day, _)| day).eq(1..=10));
+    assert!(solution(5).is_some());
+    assert!(solution(25).is_none());
+}